@@ -0,0 +1,226 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+//! Pluggable allocators.
+
+use core::{fmt, ptr};
+
+use crate::Layout;
+
+/// The error returned when an allocation fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllocError {
+    /// The requested size does not fit in a `Layout`.
+    CapacityOverflow,
+    /// The allocator could not provide a block for the layout.
+    OutOfMemory(Layout),
+}
+
+impl AllocError {
+    /// Turns the error into a panic or a call to [`handle_alloc_error`](crate::handle_alloc_error),
+    /// for infallible APIs built on top of fallible ones.
+    #[cold]
+    pub fn handle(self) -> ! {
+        match self {
+            AllocError::CapacityOverflow => panic!("capacity overflow"),
+            AllocError::OutOfMemory(layout) => crate::handle_alloc_error(layout),
+        }
+    }
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllocError::CapacityOverflow => write!(f, "capacity overflow"),
+            AllocError::OutOfMemory(layout) => write!(
+                f,
+                "memory allocation of {} bytes (align {}) failed",
+                layout.size(),
+                layout.align()
+            ),
+        }
+    }
+}
+
+impl core::error::Error for AllocError {}
+
+/// A raw memory allocator.
+///
+/// Allocation failure is reported by returning a null pointer.
+///
+/// Zero-sized layouts are allowed: an implementation may return any
+/// non-null, well-aligned pointer for them, and must accept that pointer
+/// back in [`deallocate`](RawAllocator::deallocate).
+/// ## Safety
+/// A non-null pointer returned by this allocator must point to a block that
+/// fits the requested layout and stays valid until it is passed to
+/// `deallocate`, `grow` or `shrink`, or until the allocator is dropped.
+pub unsafe trait RawAllocator {
+    /// Allocates a block of memory that fits `layout`.
+    fn allocate(&self, layout: Layout) -> *mut u8;
+
+    /// Allocates a block of memory that fits `layout`, filled with zeroes.
+    fn allocate_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.allocate(layout);
+        if !ptr.is_null() {
+            unsafe { ptr::write_bytes(ptr, 0, layout.size()) };
+        }
+        ptr
+    }
+
+    /// Frees a block of memory.
+    /// ## Safety
+    /// `ptr` must have been returned by this allocator for `layout`.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+
+    /// Grows a block of memory, moving it if needed.
+    ///
+    /// On failure the old block is left untouched and null is returned.
+    /// ## Safety
+    /// `ptr` must have been returned by this allocator for `old_layout`,
+    /// and `new_layout.size()` must not be smaller than `old_layout.size()`.
+    unsafe fn grow(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        debug_assert!(new_layout.size() >= old_layout.size());
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, old_layout.size());
+            self.deallocate(ptr, old_layout);
+        }
+        new
+    }
+
+    /// Shrinks a block of memory, moving it if needed.
+    ///
+    /// On failure the old block is left untouched and null is returned.
+    /// ## Safety
+    /// `ptr` must have been returned by this allocator for `old_layout`,
+    /// and `new_layout.size()` must not be larger than `old_layout.size()`.
+    unsafe fn shrink(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        debug_assert!(new_layout.size() <= old_layout.size());
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, new_layout.size());
+            self.deallocate(ptr, old_layout);
+        }
+        new
+    }
+}
+
+unsafe impl<A: RawAllocator + ?Sized> RawAllocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> *mut u8 {
+        (**self).allocate_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn grow(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn shrink(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        (**self).shrink(ptr, old_layout, new_layout)
+    }
+}
+
+/// The global allocator.
+///
+/// Forwards to [`malloc`](crate::malloc), [`realloc`](crate::realloc)
+/// and [`dealloc`](crate::dealloc).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Global;
+
+impl Global {
+    /// Returns a dangling pointer for a zero-sized `layout`.
+    #[inline]
+    fn dangling(layout: Layout) -> *mut u8 {
+        ptr::without_provenance_mut(layout.align())
+    }
+}
+
+unsafe impl RawAllocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == 0 {
+            return Self::dangling(layout);
+        }
+        unsafe { crate::malloc(layout) }
+    }
+
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == 0 {
+            return Self::dangling(layout);
+        }
+        unsafe { liballoc::alloc::alloc_zeroed(layout) }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() != 0 {
+            crate::dealloc(ptr, layout)
+        }
+    }
+
+    unsafe fn grow(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        if old_layout.size() == 0 {
+            return self.allocate(new_layout);
+        }
+        if old_layout.align() == new_layout.align() {
+            return crate::realloc(ptr, old_layout, new_layout.size());
+        }
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, old_layout.size());
+            self.deallocate(ptr, old_layout);
+        }
+        new
+    }
+
+    unsafe fn shrink(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        if new_layout.size() == 0 {
+            self.deallocate(ptr, old_layout);
+            return Self::dangling(new_layout);
+        }
+        if old_layout.align() == new_layout.align() {
+            return crate::realloc(ptr, old_layout, new_layout.size());
+        }
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, new_layout.size());
+            self.deallocate(ptr, old_layout);
+        }
+        new
+    }
+}
@@ -0,0 +1,370 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! A bump allocator.
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::{cmp, fmt, mem, ptr, slice, str};
+
+use crate::{AllocError, Global, Layout, RawAllocator};
+
+/// The header at the start of every chunk an [`Arena`] allocates.
+struct ChunkHeader {
+    prev: *mut ChunkHeader,
+    layout: Layout,
+}
+
+/// A position in an [`Arena`] that it can be rewound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaCheckpoint {
+    chunk: *mut u8,
+    cursor: *mut u8,
+}
+
+/// A bump allocator that carves allocations out of a buffer or out of chunks
+/// from another allocator.
+///
+/// Allocating only moves a cursor forward; memory is given back all at once
+/// by [`rewind`](Arena::rewind) or [`reset`](Arena::reset). Values put in the
+/// arena are never dropped.
+///
+/// `&Arena` is a [`RawAllocator`], so a [`ByteGuard`](crate::ByteGuard) or a
+/// [`DynArray`](crate::DynArray) can live in it. Freeing or growing the most
+/// recent allocation happens in place.
+pub struct Arena<'a, A: RawAllocator = Global> {
+    cursor: Cell<*mut u8>,
+    end: Cell<*mut u8>,
+    chunks: Cell<*mut ChunkHeader>,
+    buffer: (*mut u8, *mut u8),
+    chunk_size: usize,
+    alloc: Option<A>,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl<A: RawAllocator + Send> Send for Arena<'_, A> {}
+
+impl Arena<'static> {
+    /// The size of the chunks an arena allocates by default.
+    pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+    /// Creates an arena that allocates its chunks with [`malloc`](crate::malloc).
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<'a> Arena<'a> {
+    /// Creates an arena that carves allocations out of `buffer` and nothing else.
+    #[inline]
+    #[must_use]
+    pub fn from_buffer(buffer: &'a mut [u8]) -> Self {
+        let mut res = Self::from_buffer_in(buffer, Global);
+        res.alloc = None;
+        res
+    }
+}
+
+impl<A: RawAllocator> Arena<'static, A> {
+    /// Creates an arena that allocates its chunks in `alloc`.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self {
+            cursor: Cell::new(ptr::null_mut()),
+            end: Cell::new(ptr::null_mut()),
+            chunks: Cell::new(ptr::null_mut()),
+            buffer: (ptr::null_mut(), ptr::null_mut()),
+            chunk_size: Arena::DEFAULT_CHUNK_SIZE,
+            alloc: Some(alloc),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, A: RawAllocator> Arena<'a, A> {
+    /// Creates an arena that carves allocations out of `buffer`,
+    /// and out of chunks allocated in `alloc` once it is full.
+    #[inline]
+    #[must_use]
+    pub fn from_buffer_in(buffer: &'a mut [u8], alloc: A) -> Self {
+        let range = buffer.as_mut_ptr_range();
+        Self {
+            cursor: Cell::new(range.start),
+            end: Cell::new(range.end),
+            chunks: Cell::new(ptr::null_mut()),
+            buffer: (range.start, range.end),
+            chunk_size: Arena::DEFAULT_CHUNK_SIZE,
+            alloc: Some(alloc),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the minimum size of the chunks the arena allocates.
+    #[inline]
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Returns the number of bytes left before the arena needs a new chunk.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.end.get().addr() - self.cursor.get().addr()
+    }
+
+    /// Moves `value` into the arena.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T>(&self, value: T) -> &mut T {
+        self.try_alloc(value).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Moves `value` into the arena, returning an error if there is no room for it.
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc<T>(&self, value: T) -> Result<&mut T, AllocError> {
+        let ptr = self.try_alloc_layout(Layout::new::<T>())?.cast::<T>();
+        unsafe {
+            ptr.write(value);
+            Ok(&mut *ptr)
+        }
+    }
+
+    /// Copies `values` into the arena.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, values: &[T]) -> &mut [T] {
+        self.try_alloc_slice_copy(values).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Copies `values` into the arena, returning an error if there is no room for them.
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc_slice_copy<T: Copy>(&self, values: &[T]) -> Result<&mut [T], AllocError> {
+        let layout = Layout::for_value(values);
+        let ptr = self.try_alloc_layout(layout)?.cast::<T>();
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), ptr, values.len());
+            Ok(slice::from_raw_parts_mut(ptr, values.len()))
+        }
+    }
+
+    /// Copies `text` into the arena.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_str(&self, text: &str) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(self.alloc_slice_copy(text.as_bytes())) }
+    }
+
+    /// Allocates a block that fits `layout`, returning an error if there is no room for it.
+    pub fn try_alloc_layout(&self, layout: Layout) -> Result<*mut u8, AllocError> {
+        if layout.size() == 0 {
+            return Ok(ptr::without_provenance_mut(layout.align()));
+        }
+        if let Some(ptr) = self.bump(layout) {
+            return Ok(ptr);
+        }
+        self.grow_chunks(layout)?;
+        self.bump(layout).ok_or(AllocError::OutOfMemory(layout))
+    }
+
+    /// Returns a checkpoint to [`rewind`](Arena::rewind) to.
+    #[inline]
+    #[must_use]
+    pub fn checkpoint(&self) -> ArenaCheckpoint {
+        ArenaCheckpoint { chunk: self.chunks.get().cast(), cursor: self.cursor.get() }
+    }
+
+    /// Frees everything allocated since `checkpoint` was taken.
+    /// ## Panics
+    /// Panics if `checkpoint` was not taken from this arena, or if the arena
+    /// was rewound or reset past it.
+    pub fn rewind(&mut self, checkpoint: ArenaCheckpoint) {
+        let target: *mut ChunkHeader = checkpoint.chunk.cast();
+        let mut chunk = self.chunks.get();
+        while chunk != target {
+            assert!(!chunk.is_null(), "checkpoint does not belong to this arena");
+            chunk = unsafe { (*chunk).prev };
+        }
+        while self.chunks.get() != target {
+            unsafe { self.free_newest_chunk() };
+        }
+        let (start, end) = self.current_range();
+        assert!(
+            (start.addr()..=end.addr()).contains(&checkpoint.cursor.addr()),
+            "checkpoint does not belong to this arena"
+        );
+        self.cursor.set(start.wrapping_add(checkpoint.cursor.addr() - start.addr()));
+        self.end.set(end);
+    }
+
+    /// Frees everything in the arena, giving its chunks back to the allocator.
+    pub fn reset(&mut self) {
+        while !self.chunks.get().is_null() {
+            unsafe { self.free_newest_chunk() };
+        }
+        self.cursor.set(self.buffer.0);
+        self.end.set(self.buffer.1);
+    }
+
+    /// Allocates `layout` from the current chunk, if it fits.
+    #[inline]
+    fn bump(&self, layout: Layout) -> Option<*mut u8> {
+        let cursor = self.cursor.get();
+        let start = cursor.addr().checked_next_multiple_of(layout.align())?;
+        let new = start.checked_add(layout.size())?;
+        if cursor.is_null() || new > self.end.get().addr() {
+            return None;
+        }
+        self.cursor.set(cursor.wrapping_add(new - cursor.addr()));
+        Some(cursor.wrapping_add(start - cursor.addr()))
+    }
+
+    /// Allocates a chunk with room for `layout` and makes it current.
+    #[cold]
+    fn grow_chunks(&self, layout: Layout) -> Result<(), AllocError> {
+        let alloc = self.alloc.as_ref().ok_or(AllocError::OutOfMemory(layout))?;
+        let header = mem::size_of::<ChunkHeader>();
+        let previous = match self.chunks.get() {
+            chunk if chunk.is_null() => 0,
+            chunk => unsafe { (*chunk).layout.size() },
+        };
+        let needed = layout
+            .size()
+            .checked_add(layout.align())
+            .and_then(|size| size.checked_add(header))
+            .ok_or(AllocError::CapacityOverflow)?;
+        let size = cmp::max(cmp::max(self.chunk_size, previous.saturating_mul(2)), needed);
+        let chunk_layout = Layout::from_size_align(size, mem::align_of::<ChunkHeader>())
+            .map_err(|_| AllocError::CapacityOverflow)?;
+        let base = alloc.allocate(chunk_layout);
+        if base.is_null() {
+            return Err(AllocError::OutOfMemory(chunk_layout));
+        }
+        unsafe {
+            base.cast::<ChunkHeader>().write(ChunkHeader { prev: self.chunks.get(), layout: chunk_layout });
+        }
+        self.chunks.set(base.cast());
+        self.cursor.set(base.wrapping_add(header));
+        self.end.set(base.wrapping_add(size));
+        Ok(())
+    }
+
+    /// Frees the newest chunk. The cursor must be moved out of it afterwards.
+    unsafe fn free_newest_chunk(&self) {
+        let chunk = self.chunks.get();
+        let ChunkHeader { prev, layout } = chunk.read();
+        self.chunks.set(prev);
+        if let Some(alloc) = &self.alloc {
+            alloc.deallocate(chunk.cast(), layout);
+        }
+    }
+
+    /// Returns the usable range of the newest chunk, or of the buffer.
+    fn current_range(&self) -> (*mut u8, *mut u8) {
+        let chunk = self.chunks.get();
+        if chunk.is_null() {
+            return self.buffer;
+        }
+        let base = chunk.cast::<u8>();
+        let size = unsafe { (*chunk).layout.size() };
+        (base.wrapping_add(mem::size_of::<ChunkHeader>()), base.wrapping_add(size))
+    }
+}
+
+impl Default for Arena<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: RawAllocator> Drop for Arena<'_, A> {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+impl<A: RawAllocator> fmt::Debug for Arena<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chunks = 0;
+        let mut chunk = self.chunks.get();
+        while !chunk.is_null() {
+            chunks += 1;
+            chunk = unsafe { (*chunk).prev };
+        }
+        f.debug_struct("Arena").field("chunks", &chunks).field("remaining", &self.remaining()).finish()
+    }
+}
+
+unsafe impl<A: RawAllocator> RawAllocator for Arena<'_, A> {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        self.try_alloc_layout(layout).unwrap_or(ptr::null_mut())
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() != 0 && ptr.wrapping_add(layout.size()) == self.cursor.get() {
+            self.cursor.set(ptr);
+        }
+    }
+
+    unsafe fn grow(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        let in_place = old_layout.size() != 0
+            && ptr.wrapping_add(old_layout.size()) == self.cursor.get()
+            && ptr.addr().is_multiple_of(new_layout.align())
+            && new_layout.size() - old_layout.size() <= self.remaining();
+        if in_place {
+            self.cursor.set(ptr.wrapping_add(new_layout.size()));
+            return ptr;
+        }
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, old_layout.size());
+            self.deallocate(ptr, old_layout);
+        }
+        new
+    }
+
+    unsafe fn shrink(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        if new_layout.size() == 0 {
+            self.deallocate(ptr, old_layout);
+            return ptr::without_provenance_mut(new_layout.align());
+        }
+        if ptr.addr().is_multiple_of(new_layout.align()) {
+            if ptr.wrapping_add(old_layout.size()) == self.cursor.get() {
+                self.cursor.set(ptr.wrapping_add(new_layout.size()));
+            }
+            return ptr;
+        }
+        let new = self.allocate(new_layout);
+        if !new.is_null() {
+            ptr::copy_nonoverlapping(ptr, new, new_layout.size());
+        }
+        new
+    }
+}
@@ -0,0 +1,368 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! A binary buddy allocator.
+
+use core::alloc::GlobalAlloc;
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+use core::fmt;
+
+use bit_field::BitArray;
+
+use crate::{AllocError, Layout, RawAllocator, SpinLock, SpinLockGuard};
+
+/// The number of bits in a bitmap word.
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// A binary buddy allocator for power-of-two blocks of a region.
+///
+/// Blocks range from `2^min_order` to `2^max_order` bytes. A block is split
+/// in two buddies when a smaller one is needed, and merged with its buddy
+/// again when both are free. The free blocks of every order are tracked in a
+/// bitmap, so the region itself is never read or written: it can be memory
+/// to hand out bytes from, or physical frames that are not mapped at all.
+///
+/// Blocks are aligned to their size relative to the start of the region,
+/// so a region aligned to `2^max_order` gives naturally aligned blocks.
+pub struct BuddyAllocator<'a> {
+    base: *mut u8,
+    size: usize,
+    min_order: u32,
+    max_order: u32,
+    bitmap: &'a mut [usize],
+    free: [usize; usize::BITS as usize],
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl Send for BuddyAllocator<'_> {}
+
+impl<'a> BuddyAllocator<'a> {
+    /// Returns the number of bitmap words needed to manage `size` bytes.
+    #[must_use]
+    pub const fn bitmap_words(size: usize, min_order: u32, max_order: u32) -> usize {
+        let mut bits = 0;
+        let mut order = min_order;
+        while order <= max_order && order < usize::BITS {
+            bits += size >> order;
+            order += 1;
+        }
+        bits.div_ceil(WORD_BITS)
+    }
+
+    /// Creates an allocator that hands out blocks of `memory`.
+    /// ## Panics
+    /// Panics if the orders are out of range or if `bitmap` has fewer than
+    /// [`bitmap_words`](BuddyAllocator::bitmap_words) words.
+    #[must_use]
+    #[track_caller]
+    pub fn from_slice(memory: &'a mut [u8], min_order: u32, max_order: u32, bitmap: &'a mut [usize]) -> Self {
+        unsafe { Self::new(memory.as_mut_ptr(), memory.len(), min_order, max_order, bitmap) }
+    }
+
+    /// Creates an allocator for the `size` bytes starting at address `start`,
+    /// such as a physical memory range to hand out frames from.
+    /// ## Safety
+    /// The range must not be used by anything else while the allocator lives,
+    /// and must be valid for reads and writes if the blocks are accessed.
+    /// ## Panics
+    /// See [`from_slice`](BuddyAllocator::from_slice).
+    #[must_use]
+    #[track_caller]
+    pub unsafe fn from_addr(start: usize, size: usize, min_order: u32, max_order: u32, bitmap: &'a mut [usize]) -> Self {
+        Self::new(ptr::with_exposed_provenance_mut(start), size, min_order, max_order, bitmap)
+    }
+
+    /// Creates an allocator for the `size` bytes starting at `start`.
+    /// ## Safety
+    /// See [`from_addr`](BuddyAllocator::from_addr).
+    /// ## Panics
+    /// See [`from_slice`](BuddyAllocator::from_slice).
+    #[track_caller]
+    pub unsafe fn new(start: *mut u8, size: usize, min_order: u32, max_order: u32, bitmap: &'a mut [usize]) -> Self {
+        assert!(min_order <= max_order, "the minimum order is larger than the maximum order");
+        assert!(max_order < usize::BITS, "the maximum order does not fit in a usize");
+        let aligned = start.addr().checked_next_multiple_of(1 << min_order).unwrap_or(usize::MAX);
+        let usable = start.addr().saturating_add(size).saturating_sub(aligned);
+        let usable = usable >> min_order << min_order;
+        let words = Self::bitmap_words(usable, min_order, max_order);
+        assert!(bitmap.len() >= words, "the bitmap needs {words} words for this region");
+        bitmap[..words].fill(0);
+        let mut res = Self {
+            base: start.wrapping_add(aligned.wrapping_sub(start.addr())),
+            size: usable,
+            min_order,
+            max_order,
+            bitmap,
+            free: [0; usize::BITS as usize],
+            _marker: PhantomData,
+        };
+        let mut offset = 0;
+        while offset < usable {
+            let mut order = max_order.min(offset.trailing_zeros());
+            while offset + (1 << order) > usable {
+                order -= 1;
+            }
+            res.set_free(order, offset >> order, true);
+            offset += 1 << order;
+        }
+        res
+    }
+
+    /// Returns the smallest order of block.
+    #[inline]
+    #[must_use]
+    pub fn min_order(&self) -> u32 {
+        self.min_order
+    }
+
+    /// Returns the largest order of block.
+    #[inline]
+    #[must_use]
+    pub fn max_order(&self) -> u32 {
+        self.max_order
+    }
+
+    /// Returns the number of bytes managed by the allocator.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of free blocks of `order`.
+    #[inline]
+    #[must_use]
+    pub fn free_blocks(&self, order: u32) -> usize {
+        if order < self.min_order || order > self.max_order {
+            return 0;
+        }
+        self.free[(order - self.min_order) as usize]
+    }
+
+    /// Returns the number of free bytes.
+    #[must_use]
+    pub fn free_bytes(&self) -> usize {
+        (self.min_order..=self.max_order).map(|order| self.free_blocks(order) << order).sum()
+    }
+
+    /// Returns the order of the block used for `layout`, if there is one.
+    #[must_use]
+    pub fn order_for(&self, layout: Layout) -> Option<u32> {
+        let size = layout.size().max(layout.align()).checked_next_power_of_two()?;
+        let order = size.trailing_zeros().max(self.min_order);
+        (order <= self.max_order).then_some(order)
+    }
+
+    /// Allocates a block of `order` and returns its address.
+    pub fn allocate_block(&mut self, order: u32) -> Option<usize> {
+        self.allocate_order(order).map(|ptr| ptr.addr())
+    }
+
+    /// Frees the block of `order` at address `addr`.
+    /// ## Safety
+    /// The block must have been returned by [`allocate_block`](BuddyAllocator::allocate_block)
+    /// on this allocator for `order`.
+    pub unsafe fn deallocate_block(&mut self, addr: usize, order: u32) {
+        self.deallocate_order(addr - self.base.addr(), order);
+    }
+
+    /// Allocates a block that fits `layout`.
+    pub fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let order = self.order_for(layout).ok_or(AllocError::OutOfMemory(layout))?;
+        let ptr = self.allocate_order(order).ok_or(AllocError::OutOfMemory(layout))?;
+        if !ptr.addr().is_multiple_of(layout.align()) {
+            unsafe { self.deallocate_order(ptr.addr() - self.base.addr(), order) };
+            return Err(AllocError::OutOfMemory(layout));
+        }
+        Ok(unsafe { NonNull::new_unchecked(ptr) })
+    }
+
+    /// Frees a block.
+    /// ## Safety
+    /// `ptr` must have been returned by [`allocate`](BuddyAllocator::allocate) on this allocator for `layout`.
+    pub unsafe fn deallocate(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        let order = self.order_for(layout).unwrap_unchecked();
+        self.deallocate_order(ptr.addr().get() - self.base.addr(), order);
+    }
+
+    /// Takes a free block of `order`, splitting a larger one if needed.
+    fn allocate_order(&mut self, order: u32) -> Option<*mut u8> {
+        if order < self.min_order || order > self.max_order {
+            return None;
+        }
+        let mut found = (order..=self.max_order).find(|&order| self.free_blocks(order) > 0)?;
+        let mut index = self.find_free(found)?;
+        self.set_free(found, index, false);
+        while found > order {
+            found -= 1;
+            index *= 2;
+            self.set_free(found, index + 1, true);
+        }
+        Some(self.base.wrapping_add(index << order))
+    }
+
+    /// Gives back the block of `order` at `offset`, merging it with its free buddies.
+    unsafe fn deallocate_order(&mut self, offset: usize, order: u32) {
+        let mut order = order;
+        let mut index = offset >> order;
+        while order < self.max_order {
+            let buddy = index ^ 1;
+            if buddy >= self.blocks(order) || !self.is_free(order, buddy) {
+                break;
+            }
+            self.set_free(order, buddy, false);
+            index /= 2;
+            order += 1;
+        }
+        self.set_free(order, index, true);
+    }
+
+    /// Returns the number of whole blocks of `order` in the region.
+    #[inline]
+    fn blocks(&self, order: u32) -> usize {
+        self.size >> order
+    }
+
+    /// Returns the index of the first bitmap bit for `order`.
+    #[inline]
+    fn level_start(&self, order: u32) -> usize {
+        (self.min_order..order).map(|order| self.blocks(order)).sum()
+    }
+
+    /// Returns if block `index` of `order` is free.
+    #[inline]
+    fn is_free(&self, order: u32, index: usize) -> bool {
+        self.bitmap.get_bit(self.level_start(order) + index)
+    }
+
+    /// Marks block `index` of `order` as free or taken.
+    #[inline]
+    fn set_free(&mut self, order: u32, index: usize, free: bool) {
+        let bit = self.level_start(order) + index;
+        self.bitmap.set_bit(bit, free);
+        let count = &mut self.free[(order - self.min_order) as usize];
+        if free {
+            *count += 1;
+        } else {
+            *count -= 1;
+        }
+    }
+
+    /// Returns the index of the first free block of `order`.
+    fn find_free(&self, order: u32) -> Option<usize> {
+        let start = self.level_start(order);
+        let end = start + self.blocks(order);
+        let mut word = start / WORD_BITS;
+        while word * WORD_BITS < end {
+            let low = word * WORD_BITS;
+            let mut bits = self.bitmap[word];
+            if low < start {
+                bits &= !0 << (start - low);
+            }
+            if low + WORD_BITS > end {
+                bits &= !0 >> (low + WORD_BITS - end);
+            }
+            if bits != 0 {
+                return Some(low + bits.trailing_zeros() as usize - start);
+            }
+            word += 1;
+        }
+        None
+    }
+}
+
+impl fmt::Debug for BuddyAllocator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BuddyAllocator")
+            .field("base", &self.base)
+            .field("size", &self.size)
+            .field("min_order", &self.min_order)
+            .field("max_order", &self.max_order)
+            .field("free_bytes", &self.free_bytes())
+            .finish()
+    }
+}
+
+/// A [`BuddyAllocator`] behind a [`SpinLock`], usable as the `#[global_allocator]`.
+#[derive(Debug)]
+pub struct LockedBuddyAllocator<'a>(SpinLock<Option<BuddyAllocator<'a>>>);
+
+impl<'a> LockedBuddyAllocator<'a> {
+    /// Creates a locked allocator that fails every allocation until [`init`](LockedBuddyAllocator::init) is called.
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(SpinLock::new(None))
+    }
+
+    /// Creates a locked allocator around `buddy`.
+    #[inline]
+    #[must_use]
+    pub const fn new(buddy: BuddyAllocator<'a>) -> Self {
+        Self(SpinLock::new(Some(buddy)))
+    }
+
+    /// Replaces the allocator with `buddy`.
+    ///
+    /// Blocks of the previous allocator must not be freed through this one afterwards.
+    #[inline]
+    pub fn init(&self, buddy: BuddyAllocator<'a>) {
+        *self.lock() = Some(buddy);
+    }
+
+    /// Locks the allocator, to read its statistics or hand out frames.
+    #[inline]
+    pub fn lock(&self) -> SpinLockGuard<'_, Option<BuddyAllocator<'a>>> {
+        self.0.lock()
+    }
+}
+
+unsafe impl GlobalAlloc for LockedBuddyAllocator<'_> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match &mut *self.lock() {
+            Some(buddy) => buddy.allocate(layout).map_or(ptr::null_mut(), NonNull::as_ptr),
+            None => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if let Some(buddy) = &mut *self.lock() {
+            buddy.deallocate(NonNull::new_unchecked(ptr), layout)
+        }
+    }
+}
+
+unsafe impl RawAllocator for LockedBuddyAllocator<'_> {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        unsafe { GlobalAlloc::alloc(self, layout) }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        GlobalAlloc::dealloc(self, ptr, layout)
+    }
+}
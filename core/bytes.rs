@@ -25,39 +25,161 @@
 //! Byte Utilities
 
 use core::marker;
+use core::mem;
+use core::ptr;
 
 /// Creates a new `ByteGuard` from a pointer to a type.
+/// ## Safety
+/// `src` must point to a valid `T`. The guard owns a bitwise copy of it,
+/// so dropping the guard runs `T`'s destructor on that copy.
 #[inline]
 #[must_use = "this returns the result of the operation, without modifying the original"]
 pub unsafe fn create_object_byteguard_from_pointer<T>(src: *const T) -> ByteGuard<T> {
-    let layout = crate::Layout::new::<T>();
-    let ptr = crate::malloc(layout);
-    let mut guard: ByteGuard<T> = ByteGuard::<T>::new(ptr, layout);
-    guard.copy_from(src);
-    guard
+    create_object_byteguard_from_pointer_in(src, crate::Global)
+}
+
+/// Creates a new `ByteGuard` from a pointer to a type, allocated in `alloc`.
+/// ## Safety
+/// See [`create_object_byteguard_from_pointer`].
+#[inline]
+#[must_use = "this returns the result of the operation, without modifying the original"]
+pub unsafe fn create_object_byteguard_from_pointer_in<T, A: crate::RawAllocator>(src: *const T, alloc: A) -> ByteGuard<T, A> {
+    try_create_object_byteguard_from_pointer_in(src, alloc).unwrap_or_else(|e| e.handle())
+}
+
+/// Creates a new `ByteGuard` from a pointer to a type,
+/// returning an error if the allocation fails.
+/// ## Safety
+/// See [`create_object_byteguard_from_pointer`].
+#[inline]
+pub unsafe fn try_create_object_byteguard_from_pointer<T>(src: *const T) -> Result<ByteGuard<T>, crate::AllocError> {
+    try_create_object_byteguard_from_pointer_in(src, crate::Global)
+}
+
+/// Creates a new `ByteGuard` from a pointer to a type, allocated in `alloc`,
+/// returning an error if the allocation fails.
+/// ## Safety
+/// See [`create_object_byteguard_from_pointer`].
+#[inline]
+pub unsafe fn try_create_object_byteguard_from_pointer_in<T, A: crate::RawAllocator>(src: *const T, alloc: A) -> Result<ByteGuard<T, A>, crate::AllocError> {
+    let mut guard = ByteGuard::<T, A>::try_new_uninit_in(alloc)?;
+    ptr::copy_nonoverlapping(src, guard.as_mut_ptr() as *mut T, 1);
+    Ok(guard.assume_init())
 }
 
 /// An Object of bytes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ByteObject<'a,T> {
+///
+/// Its `Debug` output shows the bytes in hex, and `{:#?}` shows a full
+/// [`HexDump`](crate::HexDump) of them.
+///
+/// `T` may be unsized: slices, `str` and trait objects are viewed through
+/// their fat pointers, whose metadata is kept in [`ByteObject::metadata`].
+pub struct ByteObject<'a,T: ?Sized> {
     pub(crate) length: usize,
     pub(crate) bytes: &'a [u8],
     pub(crate) addr: *const T
 }
 
-/// A guard that frees the memory when dropped.
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct ByteGuard<T> {
-    pub(crate) ptr: *mut u8,
+/// A guard that owns a value on the heap, dropping and freeing it when dropped.
+///
+/// Works like a lightweight `Box`: it dereferences to the guarded value,
+/// clones it deeply, and can hold slices such as `ByteGuard<[T]>`.
+pub struct ByteGuard<T: ?Sized, A: crate::RawAllocator = crate::Global> {
+    pub(crate) ptr: ptr::NonNull<T>,
     pub(crate) layout: crate::Layout,
+    pub(crate) alloc: A,
     pub(crate) _marker: marker::PhantomData<T>,
 }
 
+/// A bitwise copy of a value that is never dropped.
+///
+/// Created by [`ByteClone::shallow_clone`](crate::prelude::ByteClone::shallow_clone).
+/// The copy borrows the original for as long as it lives, and only hands out
+/// shared references, so inspecting it cannot free anything the original owns.
+pub struct ShallowCopy<'a, T> {
+    pub(crate) value: mem::ManuallyDrop<T>,
+    pub(crate) _marker: marker::PhantomData<&'a T>,
+}
+
+/// The error returned when a pointer cannot be turned into a reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerError {
+    /// The pointer is null.
+    Null,
+    /// The pointer is not aligned for the type.
+    Misaligned {
+        /// The address of the pointer.
+        addr: usize,
+        /// The alignment the type requires.
+        align: usize,
+    },
+    /// The memory behind the pointer is not mapped with the needed access
+    /// in the current process.
+    ///
+    /// Only detected with the `std` feature on Linux.
+    Unmapped {
+        /// The address of the pointer.
+        addr: usize,
+        /// The size of the type.
+        len: usize,
+    },
+}
+
+impl core::fmt::Display for PointerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PointerError::Null => write!(f, "null pointer"),
+            PointerError::Misaligned { addr, align } => {
+                write!(f, "address {:#x} is not aligned to {} bytes", addr, align)
+            }
+            PointerError::Unmapped { addr, len } => {
+                write!(f, "{} bytes at {:#x} are not mapped", len, addr)
+            }
+        }
+    }
+}
+
+impl core::error::Error for PointerError {}
+
+/// Checks that `ptr` is non-null and aligned for `T`, and with the `std`
+/// feature on Linux, that the `T` behind it is mapped readable.
+///
+/// Passing the check does not make dereferencing `ptr` safe: the memory
+/// may still be freed, uninitialized or not hold a valid `T`.
+pub fn check_pointer<T>(ptr: *const T) -> Result<(), PointerError> {
+    check_pointer_access(ptr, false)
+}
+
+/// Like [`check_pointer`], but with the `std` feature on Linux also
+/// checks that the memory is mapped writable.
+pub fn check_pointer_mut<T>(ptr: *mut T) -> Result<(), PointerError> {
+    check_pointer_access(ptr, true)
+}
+
+fn check_pointer_access<T>(ptr: *const T, write: bool) -> Result<(), PointerError> {
+    if ptr.is_null() {
+        return Err(PointerError::Null);
+    }
+    if !ptr.is_aligned() {
+        return Err(PointerError::Misaligned { addr: ptr.addr(), align: mem::align_of::<T>() });
+    }
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    if !crate::procmaps::is_mapped(ptr.addr(), mem::size_of::<T>(), write) {
+        return Err(PointerError::Unmapped { addr: ptr.addr(), len: mem::size_of::<T>() });
+    }
+    let _ = write;
+    Ok(())
+}
+
 /// ## GO
 /// **G**et **O**bject from const pointer.
 /// 
 /// Returns a mutable reference to the object.
+/// In debug builds, panics if `ptr` is null;
+/// see [`try_go`] for a checked version.
+#[track_caller]
 pub const unsafe fn go<'a, T>(ptr: *const T) -> &'a T {
+    debug_assert!(!ptr.is_null(), "null pointer passed to go");
     &*ptr
 }
 
@@ -65,6 +187,28 @@ pub const unsafe fn go<'a, T>(ptr: *const T) -> &'a T {
 /// **G**et **O**bject **M**ut from mutable pointer.
 /// 
 /// Returns a mutable reference to the object.
+/// In debug builds, panics if `ptr` is null;
+/// see [`try_gom`] for a checked version.
+#[track_caller]
 pub const unsafe fn gom<'a, T>(ptr: *mut T) -> &'a mut T {
+    debug_assert!(!ptr.is_null(), "null pointer passed to gom");
     &mut *ptr
 }
+
+/// Like [`go`], but returns an error if [`check_pointer`] fails.
+/// ## Safety
+/// If the check passes, `ptr` must still point to a valid `T` that is not
+/// mutated for `'a`.
+pub unsafe fn try_go<'a, T>(ptr: *const T) -> Result<&'a T, PointerError> {
+    check_pointer(ptr)?;
+    Ok(&*ptr)
+}
+
+/// Like [`gom`], but returns an error if [`check_pointer_mut`] fails.
+/// ## Safety
+/// If the check passes, `ptr` must still point to a valid `T` that is not
+/// accessed through any other pointer for `'a`.
+pub unsafe fn try_gom<'a, T>(ptr: *mut T) -> Result<&'a mut T, PointerError> {
+    check_pointer_mut(ptr)?;
+    Ok(&mut *ptr)
+}
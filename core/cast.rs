@@ -0,0 +1,262 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Zero-copy casting between bytes and typed values.
+
+use core::{fmt, mem, slice};
+
+use crate::{FromBytes, NoUninit};
+
+/// The error returned when bytes cannot be cast to a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastError {
+    /// The buffer does not have the expected length.
+    Size {
+        /// The number of bytes that were needed.
+        expected: usize,
+        /// The number of bytes that were available.
+        actual: usize,
+    },
+    /// The buffer is not aligned for the type.
+    Alignment {
+        /// The alignment the type requires.
+        align: usize,
+        /// The address of the buffer.
+        addr: usize,
+    },
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::Size { expected, actual } => {
+                write!(f, "expected {} bytes, found {}", expected, actual)
+            }
+            CastError::Alignment { align, addr } => {
+                write!(f, "address {:#x} is not aligned to {} bytes", addr, align)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CastError {}
+
+/// A value that can be viewed as bytes.
+///
+/// Implemented for every [`NoUninit`] type and slices of them;
+/// implement `NoUninit` with `#[derive(AsBytes)]`.
+pub trait AsBytes {
+    /// Returns the bytes of the value.
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl<T: NoUninit> AsBytes for T {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self as *const T as *const u8, mem::size_of::<T>()) }
+    }
+}
+
+impl<T: NoUninit> AsBytes for [T] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr() as *const u8, mem::size_of_val(self)) }
+    }
+}
+
+/// Returns the bytes of a value, which may be modified in place.
+///
+/// `T` must be [`FromBytes`] as well as [`NoUninit`],
+/// so that any bytes written leave a valid value behind.
+#[inline]
+pub fn as_bytes_mut<T: FromBytes + NoUninit>(value: &mut T) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(value as *mut T as *mut u8, mem::size_of::<T>()) }
+}
+
+/// Returns the bytes of a slice of values, which may be modified in place.
+#[inline]
+pub fn slice_as_bytes_mut<T: FromBytes + NoUninit>(values: &mut [T]) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, mem::size_of_val(values)) }
+}
+
+/// Checks that `bytes` holds exactly `expected` bytes aligned for `T`.
+#[inline]
+fn check<T>(bytes: &[u8], expected: usize) -> Result<(), CastError> {
+    if bytes.len() != expected {
+        return Err(CastError::Size { expected, actual: bytes.len() });
+    }
+    let addr = bytes.as_ptr() as usize;
+    if !addr.is_multiple_of(mem::align_of::<T>()) {
+        return Err(CastError::Alignment { align: mem::align_of::<T>(), addr });
+    }
+    Ok(())
+}
+
+/// Splits off the first `size_of::<T>()` bytes, or fails if there are not enough.
+#[inline]
+fn split_prefix<T>(len: usize) -> Result<usize, CastError> {
+    let expected = mem::size_of::<T>();
+    if len < expected {
+        return Err(CastError::Size { expected, actual: len });
+    }
+    Ok(expected)
+}
+
+/// Casts `bytes` to a reference to `T`.
+///
+/// Fails unless `bytes` is exactly `size_of::<T>()` long and aligned for `T`.
+#[inline]
+pub fn ref_from_bytes<T: FromBytes>(bytes: &[u8]) -> Result<&T, CastError> {
+    check::<T>(bytes, mem::size_of::<T>())?;
+    Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+}
+
+/// Casts `bytes` to a mutable reference to `T`.
+///
+/// Fails unless `bytes` is exactly `size_of::<T>()` long and aligned for `T`.
+#[inline]
+pub fn mut_from_bytes<T: FromBytes + NoUninit>(bytes: &mut [u8]) -> Result<&mut T, CastError> {
+    check::<T>(bytes, mem::size_of::<T>())?;
+    Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut T) })
+}
+
+/// Casts the start of `bytes` to a reference to `T`, returning the rest.
+#[inline]
+pub fn ref_from_prefix<T: FromBytes>(bytes: &[u8]) -> Result<(&T, &[u8]), CastError> {
+    let (head, rest) = bytes.split_at(split_prefix::<T>(bytes.len())?);
+    Ok((ref_from_bytes(head)?, rest))
+}
+
+/// Casts the start of `bytes` to a mutable reference to `T`, returning the rest.
+#[inline]
+pub fn mut_from_prefix<T: FromBytes + NoUninit>(bytes: &mut [u8]) -> Result<(&mut T, &mut [u8]), CastError> {
+    let (head, rest) = bytes.split_at_mut(split_prefix::<T>(bytes.len())?);
+    Ok((mut_from_bytes(head)?, rest))
+}
+
+/// Casts the end of `bytes` to a reference to `T`, returning what comes before it.
+#[inline]
+pub fn ref_from_suffix<T: FromBytes>(bytes: &[u8]) -> Result<(&[u8], &T), CastError> {
+    let at = bytes.len() - split_prefix::<T>(bytes.len())?;
+    let (rest, tail) = bytes.split_at(at);
+    Ok((rest, ref_from_bytes(tail)?))
+}
+
+/// Casts the end of `bytes` to a mutable reference to `T`, returning what comes before it.
+#[inline]
+pub fn mut_from_suffix<T: FromBytes + NoUninit>(bytes: &mut [u8]) -> Result<(&mut [u8], &mut T), CastError> {
+    let at = bytes.len() - split_prefix::<T>(bytes.len())?;
+    let (rest, tail) = bytes.split_at_mut(at);
+    Ok((rest, mut_from_bytes(tail)?))
+}
+
+/// Casts `bytes` to a slice of `T`.
+///
+/// Fails unless the length of `bytes` is a multiple of `size_of::<T>()`
+/// and `bytes` is aligned for `T`.
+/// ## Panics
+/// Panics if `T` is zero-sized.
+#[inline]
+pub fn slice_from_bytes<T: FromBytes>(bytes: &[u8]) -> Result<&[T], CastError> {
+    let len = slice_len::<T>(bytes.len())?;
+    check::<T>(bytes, len * mem::size_of::<T>())?;
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, len) })
+}
+
+/// Casts `bytes` to a mutable slice of `T`.
+///
+/// Fails unless the length of `bytes` is a multiple of `size_of::<T>()`
+/// and `bytes` is aligned for `T`.
+/// ## Panics
+/// Panics if `T` is zero-sized.
+#[inline]
+pub fn slice_from_bytes_mut<T: FromBytes + NoUninit>(bytes: &mut [u8]) -> Result<&mut [T], CastError> {
+    let len = slice_len::<T>(bytes.len())?;
+    check::<T>(bytes, len * mem::size_of::<T>())?;
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) })
+}
+
+/// Returns how many `T` fit in `len` bytes, failing if there are bytes left over.
+#[inline]
+fn slice_len<T>(len: usize) -> Result<usize, CastError> {
+    let size = mem::size_of::<T>();
+    assert!(size != 0, "cannot cast bytes to a slice of zero-sized values");
+    if !len.is_multiple_of(size) {
+        return Err(CastError::Size { expected: len - len % size, actual: len });
+    }
+    Ok(len / size)
+}
+
+/// Copies a `T` out of the start of `bytes`, which does not need to be aligned.
+#[inline]
+pub fn read_from_bytes<T: FromBytes>(bytes: &[u8]) -> Result<T, CastError> {
+    split_prefix::<T>(bytes.len())?;
+    Ok(unsafe { (bytes.as_ptr() as *const T).read_unaligned() })
+}
+
+/// Fails the build unless `Src` and `Dst` have the same size.
+const fn assert_same_size<Src, Dst>() {
+    assert!(mem::size_of::<Src>() == mem::size_of::<Dst>(), "source and target types have different sizes");
+}
+
+/// Fails the build unless `Src` and `Dst` have the same size and alignment.
+const fn assert_same_layout<Src, Dst>() {
+    assert_same_size::<Src, Dst>();
+    assert!(mem::align_of::<Src>() == mem::align_of::<Dst>(), "source and target types have different alignments");
+}
+
+/// Reinterprets a value as another type with the same size.
+///
+/// Unlike `core::mem::transmute`, this also works in generic code: the
+/// sizes are compared when the function is instantiated, and a mismatch
+/// fails the build. The value is copied, so the alignments may differ.
+/// ## Safety
+/// The bytes of `src` must be a valid `Dst`.
+#[inline]
+pub unsafe fn transmute_checked<Src, Dst>(src: Src) -> Dst {
+    const { assert_same_size::<Src, Dst>() };
+    mem::transmute_copy(&mem::ManuallyDrop::new(src))
+}
+
+/// Reinterprets a reference as a reference to another type with the same
+/// size and alignment, both checked when the function is instantiated.
+/// ## Safety
+/// The bytes behind `src` must be a valid `Dst`.
+#[inline]
+pub unsafe fn transmute_ref<Src, Dst>(src: &Src) -> &Dst {
+    const { assert_same_layout::<Src, Dst>() };
+    &*(src as *const Src as *const Dst)
+}
+
+/// Reinterprets a mutable reference as a mutable reference to another type
+/// with the same size and alignment, checked like [`transmute_ref`].
+/// ## Safety
+/// The bytes behind `src` must be a valid `Dst`, and any `Dst` written
+/// through the result must be a valid `Src`.
+#[inline]
+pub unsafe fn transmute_mut<Src, Dst>(src: &mut Src) -> &mut Dst {
+    const { assert_same_layout::<Src, Dst>() };
+    &mut *(src as *mut Src as *mut Dst)
+}
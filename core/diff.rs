@@ -0,0 +1,230 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Byte-level diffs and patches.
+
+use core::{fmt, ops::Range};
+
+use crate::{CastError, DynArray, FieldLayout, FromBytes, MemLayout, NoUninit};
+
+/// The bytes that differ between two buffers of the same length.
+///
+/// Neighbouring changed bytes are coalesced into one range.
+/// `{}` prints every changed range as rows of old and new bytes side by side.
+#[derive(Debug, Clone)]
+pub struct ByteDiff<'a> {
+    old: &'a [u8],
+    new: &'a [u8],
+    ranges: DynArray<Range<usize>>,
+}
+
+impl<'a> ByteDiff<'a> {
+    /// Compares `old` with `new`.
+    /// ## Panics
+    /// Panics if `old` and `new` have different lengths.
+    #[must_use]
+    pub fn new(old: &'a [u8], new: &'a [u8]) -> Self {
+        assert_eq!(old.len(), new.len(), "cannot diff buffers of different lengths");
+        let mut ranges = DynArray::<Range<usize>>::new();
+        for (i, (a, b)) in old.iter().zip(new).enumerate() {
+            if a == b {
+                continue;
+            }
+            match ranges.as_mut_slice().last_mut() {
+                Some(last) if last.end == i => last.end += 1,
+                _ => ranges.push(i..i + 1),
+            }
+        }
+        Self { old, new, ranges }
+    }
+
+    /// Returns the changed ranges, in order.
+    #[inline]
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Returns if nothing changed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of bytes that changed.
+    #[must_use]
+    pub fn changed_bytes(&self) -> usize {
+        self.ranges.iter().map(|r| r.len()).sum()
+    }
+
+    /// Returns the bytes before the change.
+    #[inline]
+    #[must_use]
+    pub const fn before(&self) -> &'a [u8] {
+        self.old
+    }
+
+    /// Returns the bytes after the change.
+    #[inline]
+    #[must_use]
+    pub const fn after(&self) -> &'a [u8] {
+        self.new
+    }
+
+    /// Returns the fields of `T` that contain a changed byte, in declaration order.
+    ///
+    /// Changes that only touch padding do not show up here.
+    pub fn changed_fields<T: MemLayout>(&self) -> impl Iterator<Item = &'static FieldLayout> + '_ {
+        T::FIELDS.iter().filter(move |field| {
+            let span = field.range();
+            self.ranges.iter().any(|r| r.start < span.end && span.start < r.end)
+        })
+    }
+
+    /// Returns a patch that turns the old bytes into the new ones.
+    #[must_use]
+    pub fn to_patch(&self) -> BytePatch {
+        let mut bytes = DynArray::with_capacity(self.changed_bytes());
+        for range in self.ranges() {
+            bytes.extend_from_slice(&self.new[range.clone()]);
+        }
+        BytePatch { len: self.new.len(), ranges: self.ranges.clone(), bytes }
+    }
+}
+
+/// Writes `bytes` in hex, padded to `width` bytes.
+fn hex_column(f: &mut fmt::Formatter<'_>, bytes: &[u8], width: usize) -> fmt::Result {
+    for i in 0..width {
+        if i != 0 {
+            f.write_str(" ")?;
+        }
+        match bytes.get(i) {
+            Some(b) => write!(f, "{:02x}", b)?,
+            None => f.write_str("  ")?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for ByteDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ROW: usize = 8;
+        let mut first = true;
+        for range in self.ranges() {
+            let mut start = range.start;
+            while start < range.end {
+                let end = core::cmp::min(start + ROW, range.end);
+                if !first {
+                    f.write_str("\n")?;
+                }
+                first = false;
+                write!(f, "{:08x}  ", start)?;
+                hex_column(f, &self.old[start..end], ROW)?;
+                f.write_str(" | ")?;
+                hex_column(f, &self.new[start..end], end - start)?;
+                start = end;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A set of byte ranges to overwrite in a buffer of a known length.
+///
+/// Created by [`ByteDiff::to_patch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BytePatch {
+    len: usize,
+    ranges: DynArray<Range<usize>>,
+    bytes: DynArray<u8>,
+}
+
+impl BytePatch {
+    /// Returns the length of the buffers the patch applies to.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns if the patch changes nothing.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the ranges the patch overwrites, in order.
+    #[inline]
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Overwrites the patched ranges of `target`.
+    ///
+    /// Fails without writing anything if `target` does not have the length
+    /// the patch was made for.
+    pub fn apply_to_bytes(&self, target: &mut [u8]) -> Result<(), CastError> {
+        unsafe { self.apply_to_ptr(target.as_mut_ptr(), target.len()) }
+    }
+
+    /// Copies the patched bytes into the `len` bytes behind `target`,
+    /// without ever reading them or making a slice over them.
+    unsafe fn apply_to_ptr(&self, target: *mut u8, len: usize) -> Result<(), CastError> {
+        if len != self.len {
+            return Err(CastError::Size { expected: self.len, actual: len });
+        }
+        let mut bytes = &self.bytes[..];
+        for range in self.ranges() {
+            let (head, rest) = bytes.split_at(range.len());
+            core::ptr::copy_nonoverlapping(head.as_ptr(), target.add(range.start), head.len());
+            bytes = rest;
+        }
+        Ok(())
+    }
+
+    /// Overwrites the patched bytes of `target`.
+    ///
+    /// Works with a [`ByteGuard`](crate::ByteGuard) through `&mut *guard`.
+    /// Fails without writing anything if `T` does not have the size the
+    /// patch was made for.
+    #[inline]
+    pub fn apply<T: FromBytes + NoUninit>(&self, target: &mut T) -> Result<(), CastError> {
+        self.apply_to_bytes(crate::as_bytes_mut(target))
+    }
+
+    /// Overwrites the patched bytes of `target`, which may be any type.
+    ///
+    /// Fails without writing anything if `T` does not have the size the
+    /// patch was made for.
+    /// ## Safety
+    /// The patched value must be a valid `T`, which holds when the patch
+    /// was made from two valid values of `T` and `target` equals the first.
+    pub unsafe fn apply_unchecked<T>(&self, target: &mut T) -> Result<(), CastError> {
+        self.apply_to_ptr(target as *mut T as *mut u8, core::mem::size_of::<T>())
+    }
+}
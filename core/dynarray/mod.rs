@@ -1,20 +1,543 @@
 //! Dynamic array.
 
+use core::ops::{Deref, DerefMut};
+use core::{fmt, hash, iter, mem, ptr, slice};
+
+use crate::{AllocError, Global, RawAllocator};
+
 mod raw;
 #[cfg(feature = "reveal_hidden")]
 pub use raw::RawDynArray;
 #[cfg(not(feature = "reveal_hidden"))]
 pub(crate) use raw::RawDynArray;
 
+mod rel;
+pub use rel::RelDynArray;
+
 /// A dynamic array.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DynArray<'a, T> {
-    buf: RawDynArray<'a, T>,
+///
+/// An owning, growable array. By default it is backed by [`malloc`](crate::malloc),
+/// [`realloc`](crate::realloc) and [`dealloc`](crate::dealloc), but any
+/// [`RawAllocator`] can be used instead.
+pub struct DynArray<T, A: RawAllocator = Global> {
+    buf: RawDynArray<T, A>,
     len: usize,
 }
 
-impl<T: core::fmt::Debug> core::fmt::Debug for DynArray<'_,T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "DynArray {{ buf: {:?} }}", self.buf)
+impl<T> DynArray<T> {
+    /// Creates a new, empty `DynArray` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a new, empty `DynArray` with room for at least `capacity` values.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    /// Creates a new, empty `DynArray` with room for at least `capacity` values,
+    /// returning an error if the allocation fails.
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, AllocError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: RawAllocator> DynArray<T, A> {
+    /// Creates a new, empty `DynArray` in `alloc` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self { buf: RawDynArray::new_in(alloc), len: 0 }
+    }
+
+    /// Creates a new, empty `DynArray` in `alloc` with room for at least `capacity` values.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self { buf: RawDynArray::with_capacity_in(capacity, alloc), len: 0 }
+    }
+
+    /// Creates a new, empty `DynArray` in `alloc` with room for at least `capacity` values,
+    /// returning an error if the allocation fails.
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, AllocError> {
+        Ok(Self { buf: RawDynArray::try_with_capacity_in(capacity, alloc)?, len: 0 })
+    }
+
+    /// Returns the allocator backing the `DynArray`.
+    #[inline]
+    #[must_use]
+    pub const fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    /// returns the number of values in the `DynArray`
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// returns if the `DynArray` is empty
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// returns the number of values the `DynArray` can hold without reallocating
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns a const pointer to the first value.
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf.ptr()
+    }
+
+    /// Returns a mutable pointer to the first value.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.ptr()
+    }
+
+    /// Returns the values as a slice.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Returns the values as a mutable slice.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    /// Reserves room for at least `additional` more values.
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(self.len, additional);
+    }
+
+    /// Reserves room for at least `additional` more values,
+    /// returning an error if the allocation fails.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.buf.try_reserve(self.len, additional)
+    }
+
+    /// Reserves room for exactly `additional` more values.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.buf.reserve_exact(self.len, additional);
+    }
+
+    /// Reserves room for exactly `additional` more values,
+    /// returning an error if the allocation fails.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.buf.try_reserve_exact(self.len, additional)
+    }
+
+    /// Shrinks the capacity as close to the length as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to(self.len);
+    }
+
+    /// Shrinks the capacity to `max(capacity, len)`.
+    pub fn shrink_to(&mut self, capacity: usize) {
+        self.buf.shrink_to(core::cmp::max(capacity, self.len));
+    }
+
+    /// Forces the length of the `DynArray` to `len`.
+    /// ## Safety
+    /// `len` must not exceed the capacity,
+    /// and the values in `old_len..len` must be initialized.
+    #[inline]
+    pub unsafe fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+
+    /// Appends a value to the end.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.reserve(1);
+        }
+        unsafe { self.push_unchecked(value) }
+    }
+
+    /// Appends a value to the end, returning an error if the allocation fails.
+    ///
+    /// On failure the `DynArray` is left untouched and `value` is dropped.
+    pub fn try_push(&mut self, value: T) -> Result<(), AllocError> {
+        if self.len == self.capacity() {
+            self.try_reserve(1)?;
+        }
+        unsafe { self.push_unchecked(value) };
+        Ok(())
+    }
+
+    /// Appends a value to the end without checking the capacity.
+    #[inline]
+    unsafe fn push_unchecked(&mut self, value: T) {
+        ptr::write(self.as_mut_ptr().add(self.len), value);
+        self.len += 1;
+    }
+
+    /// Removes the last value and returns it, or `None` if empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(ptr::read(self.as_ptr().add(self.len))) }
+    }
+
+    /// Inserts a value at `index`, shifting everything after it to the right.
+    /// ## Panics
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len, "insertion index (is {}) should be <= len (is {})", index, self.len);
+        if self.len == self.capacity() {
+            self.reserve(1);
+        }
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            ptr::write(p, value);
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the value at `index`, shifting everything after it to the left.
+    /// ## Panics
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "removal index (is {}) should be < len (is {})", index, self.len);
+        unsafe {
+            self.len -= 1;
+            let p = self.as_mut_ptr().add(index);
+            let res = ptr::read(p);
+            ptr::copy(p.add(1), p, self.len - index);
+            res
+        }
+    }
+
+    /// Removes and returns the value at `index`, replacing it with the last value.
+    /// ## Panics
+    /// Panics if `index >= len`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "swap_remove index (is {}) should be < len (is {})", index, self.len);
+        unsafe {
+            self.len -= 1;
+            let base = self.as_mut_ptr();
+            let res = ptr::read(base.add(index));
+            ptr::copy(base.add(self.len), base.add(index), 1);
+            res
+        }
+    }
+
+    /// Drops every value past `len`, keeping the capacity.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.as_mut_ptr().add(len), self.len - len);
+            self.len = len;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops every value, keeping the capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the values for which `keep` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        // Restores a valid array if `keep` panics: the unvisited tail is moved
+        // down behind the kept prefix so nothing is leaked or dropped twice.
+        struct Guard<'a, T, A: RawAllocator> {
+            array: &'a mut DynArray<T, A>,
+            len: usize,
+            visited: usize,
+            kept: usize,
+        }
+
+        impl<T, A: RawAllocator> Drop for Guard<'_, T, A> {
+            fn drop(&mut self) {
+                unsafe {
+                    let base = self.array.as_mut_ptr();
+                    ptr::copy(base.add(self.visited), base.add(self.kept), self.len - self.visited);
+                }
+                self.array.len = self.kept + (self.len - self.visited);
+            }
+        }
+
+        let len = self.len;
+        self.len = 0;
+        let mut guard = Guard { array: self, len, visited: 0, kept: 0 };
+        while guard.visited < len {
+            unsafe {
+                let base = guard.array.as_mut_ptr();
+                let p = base.add(guard.visited);
+                if keep(&*p) {
+                    ptr::copy(p, base.add(guard.kept), 1);
+                    guard.kept += 1;
+                    guard.visited += 1;
+                } else {
+                    guard.visited += 1;
+                    ptr::drop_in_place(p);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Clone, A: RawAllocator> DynArray<T, A> {
+    /// Clones and appends every value in `other`.
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.reserve(other.len());
+        for value in other {
+            self.push(value.clone());
+        }
+    }
+
+    /// Resizes to `len`, filling new slots with clones of `value`.
+    pub fn resize(&mut self, len: usize, value: T) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        self.reserve(len - self.len);
+        while self.len < len {
+            self.push(value.clone());
+        }
+    }
+}
+
+impl<T, A: RawAllocator> Drop for DynArray<T, A> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+impl<T> Default for DynArray<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: RawAllocator> Deref for DynArray<T, A> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: RawAllocator> DerefMut for DynArray<T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, A: RawAllocator> AsRef<[T]> for DynArray<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: RawAllocator> AsMut<[T]> for DynArray<T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone, A: RawAllocator + Clone> Clone for DynArray<T, A> {
+    fn clone(&self) -> Self {
+        let mut res = Self::with_capacity_in(self.len, self.allocator().clone());
+        res.extend_from_slice(self);
+        res
+    }
+}
+
+impl<T: fmt::Debug, A: RawAllocator> fmt::Debug for DynArray<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, A: RawAllocator, B: RawAllocator> PartialEq<DynArray<T, B>> for DynArray<T, A> {
+    fn eq(&self, other: &DynArray<T, B>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq, A: RawAllocator> PartialEq<[T]> for DynArray<T, A> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq, A: RawAllocator, const N: usize> PartialEq<[T; N]> for DynArray<T, A> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, A: RawAllocator> Eq for DynArray<T, A> {}
+
+impl<T: PartialOrd, A: RawAllocator> PartialOrd for DynArray<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, A: RawAllocator> Ord for DynArray<T, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: hash::Hash, A: RawAllocator> hash::Hash for DynArray<T, A> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T, A: RawAllocator> Extend<T> for DynArray<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: RawAllocator> Extend<&'a T> for DynArray<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> iter::FromIterator<T> for DynArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T: Clone> From<&[T]> for DynArray<T> {
+    fn from(values: &[T]) -> Self {
+        let mut res = Self::with_capacity(values.len());
+        res.extend_from_slice(values);
+        res
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for DynArray<T> {
+    fn from(values: [T; N]) -> Self {
+        let mut res = Self::with_capacity(N);
+        res.extend(values);
+        res
+    }
+}
+
+impl<'a, T, A: RawAllocator> IntoIterator for &'a DynArray<T, A> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: RawAllocator> IntoIterator for &'a mut DynArray<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, A: RawAllocator> IntoIterator for DynArray<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+    fn into_iter(self) -> Self::IntoIter {
+        let this = mem::ManuallyDrop::new(self);
+        IntoIter {
+            buf: unsafe { ptr::read(&this.buf) },
+            start: 0,
+            end: this.len,
+        }
+    }
+}
+
+/// An owning iterator over the values of a [`DynArray`].
+pub struct IntoIter<T, A: RawAllocator = Global> {
+    buf: RawDynArray<T, A>,
+    start: usize,
+    end: usize,
+}
+
+impl<T, A: RawAllocator> IntoIter<T, A> {
+    /// Returns the values that have not been yielded yet.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.buf.ptr().add(self.start), self.end - self.start) }
+    }
+}
+
+impl<T, A: RawAllocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        unsafe { Some(ptr::read(self.buf.ptr().add(self.start - 1))) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, A: RawAllocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        unsafe { Some(ptr::read(self.buf.ptr().add(self.end))) }
+    }
+}
+
+impl<T, A: RawAllocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: RawAllocator> iter::FusedIterator for IntoIter<T, A> {}
+
+impl<T, A: RawAllocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(self.buf.ptr().add(self.start), self.end - self.start);
+            ptr::drop_in_place(rest);
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: RawAllocator> fmt::Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
     }
 }
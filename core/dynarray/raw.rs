@@ -1,13 +1,182 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+use core::mem;
+
+use crate::{AllocError, Global, Layout, RawAllocator};
 
 /// A raw dynamic array.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct RawDynArray<'a, T> {
-    buf: &'a [T],
-    len: usize,
+///
+/// Owns an allocation large enough for `capacity` values of `T`,
+/// but does not track which of them are initialized.
+pub struct RawDynArray<T, A: RawAllocator = Global> {
+    ptr: NonNull<T>,
+    cap: usize,
+    alloc: A,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send, A: RawAllocator + Send> Send for RawDynArray<T, A> {}
+unsafe impl<T: Sync, A: RawAllocator + Sync> Sync for RawDynArray<T, A> {}
+
+impl<T> RawDynArray<T> {
+    /// Creates a new `RawDynArray` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: RawAllocator> RawDynArray<T, A> {
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    /// Creates a new `RawDynArray` in `alloc` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            cap: if Self::IS_ZST { usize::MAX } else { 0 },
+            alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new `RawDynArray` in `alloc` with room for exactly `capacity` values.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::try_with_capacity_in(capacity, alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Creates a new `RawDynArray` in `alloc` with room for exactly `capacity` values,
+    /// returning an error if the allocation fails.
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, AllocError> {
+        let mut res = Self::new_in(alloc);
+        if !Self::IS_ZST && capacity > 0 {
+            res.try_set_capacity(capacity)?;
+        }
+        Ok(res)
+    }
+
+    /// Returns a pointer to the start of the allocation.
+    #[inline]
+    #[must_use]
+    pub const fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the allocator backing the array.
+    #[inline]
+    #[must_use]
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns the number of values the allocation can hold.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Makes sure there is room for at least `len + additional` values,
+    /// growing the allocation geometrically if there is not.
+    pub fn reserve(&mut self, len: usize, additional: usize) {
+        self.try_reserve(len, additional).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Like [`reserve`](RawDynArray::reserve), but returns an error if the allocation fails.
+    pub fn try_reserve(&mut self, len: usize, additional: usize) -> Result<(), AllocError> {
+        let required = len.checked_add(additional).ok_or(AllocError::CapacityOverflow)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+        let doubled = self.cap.saturating_mul(2);
+        self.try_set_capacity(core::cmp::max(core::cmp::max(doubled, required), 4))
+    }
+
+    /// Makes sure there is room for exactly `len + additional` values.
+    pub fn reserve_exact(&mut self, len: usize, additional: usize) {
+        self.try_reserve_exact(len, additional).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Like [`reserve_exact`](RawDynArray::reserve_exact), but returns an error if the allocation fails.
+    pub fn try_reserve_exact(&mut self, len: usize, additional: usize) -> Result<(), AllocError> {
+        let required = len.checked_add(additional).ok_or(AllocError::CapacityOverflow)?;
+        if required > self.cap {
+            self.try_set_capacity(required)?;
+        }
+        Ok(())
+    }
+
+    /// Shrinks the allocation so that it holds exactly `capacity` values.
+    ///
+    /// Does nothing if `capacity` is not smaller than the current capacity.
+    pub fn shrink_to(&mut self, capacity: usize) {
+        if !Self::IS_ZST && capacity < self.cap {
+            self.try_set_capacity(capacity).unwrap_or_else(|e| e.handle());
+        }
+    }
+
+    /// Moves the allocation to one that holds exactly `capacity` values.
+    fn try_set_capacity(&mut self, capacity: usize) -> Result<(), AllocError> {
+        if Self::IS_ZST {
+            return Ok(());
+        }
+        if capacity == 0 {
+            unsafe { self.free() };
+            self.ptr = NonNull::dangling();
+            self.cap = 0;
+            return Ok(());
+        }
+        let new_layout = Layout::array::<T>(capacity).map_err(|_| AllocError::CapacityOverflow)?;
+        let old_ptr = self.ptr.as_ptr() as *mut u8;
+        let ptr = if self.cap == 0 {
+            self.alloc.allocate(new_layout)
+        } else if capacity > self.cap {
+            unsafe { self.alloc.grow(old_ptr, self.layout(), new_layout) }
+        } else {
+            unsafe { self.alloc.shrink(old_ptr, self.layout(), new_layout) }
+        };
+        match NonNull::new(ptr as *mut T) {
+            Some(ptr) => {
+                self.ptr = ptr;
+                self.cap = capacity;
+                Ok(())
+            }
+            None => Err(AllocError::OutOfMemory(new_layout)),
+        }
+    }
+
+    /// Returns the layout of the current allocation.
+    #[inline]
+    fn layout(&self) -> Layout {
+        unsafe { Layout::from_size_align_unchecked(mem::size_of::<T>() * self.cap, mem::align_of::<T>()) }
+    }
+
+    /// Frees the allocation, if there is one.
+    unsafe fn free(&mut self) {
+        if !Self::IS_ZST && self.cap > 0 {
+            self.alloc.deallocate(self.ptr.as_ptr() as *mut u8, self.layout());
+        }
+    }
+}
+
+impl<T> Default for RawDynArray<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: RawAllocator> Drop for RawDynArray<T, A> {
+    fn drop(&mut self) {
+        unsafe { self.free() }
+    }
 }
 
-impl<T: core::fmt::Debug> core::fmt::Debug for RawDynArray<'_,T> {
+impl<T, A: RawAllocator> core::fmt::Debug for RawDynArray<T, A> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "RawDynArray {{ buf: {:?}, len: {} }}", self.buf, self.len)
+        write!(f, "RawDynArray {{ ptr: {:?}, cap: {} }}", self.ptr, self.cap)
     }
 }
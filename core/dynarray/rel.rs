@@ -0,0 +1,147 @@
+//! Position-independent dynamic array.
+
+use core::{fmt, mem, ptr, slice};
+
+use super::DynArray;
+use crate::{FromBytes, NoUninit, Pod, RelOffset, RelSlice, Zeroable};
+
+/// A fixed-capacity dynamic array whose storage is found through a [`RelSlice`].
+///
+/// The array and its storage can live in a shared mapping or a snapshot
+/// and be moved together to another address. It never allocates: the storage
+/// is handed to it with [`set_storage`](RelDynArray::set_storage), and
+/// [`to_dynarray`](RelDynArray::to_dynarray) copies the values out into a
+/// [`DynArray`]. Values are not dropped when the array is; use
+/// [`clear`](RelDynArray::clear) for that.
+#[repr(C)]
+pub struct RelDynArray<T, I: RelOffset = i32> {
+    buf: RelSlice<T, I>,
+    len: I,
+}
+
+impl<T, I: RelOffset> RelDynArray<T, I> {
+    /// Creates an array without storage.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { buf: RelSlice::empty(), len: I::zeroed() }
+    }
+
+    /// Returns the number of values in the array.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        // A length read from arbitrary bytes may be negative.
+        usize::try_from(self.len.to_isize()).unwrap_or(0)
+    }
+
+    /// Returns if the array has no values.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of values the storage can hold.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns if there is no room for another value.
+    #[inline]
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    /// Hands `storage` to the array and empties it.
+    ///
+    /// Values in the previous storage are forgotten, not dropped.
+    /// ## Safety
+    /// `storage` must live in the same allocation as `self`, and must not be
+    /// used through anything else for as long as the array uses it.
+    /// ## Panics
+    /// Panics if the distance to `storage` or its length does not fit in `I`.
+    #[track_caller]
+    pub unsafe fn set_storage(&mut self, storage: &mut [mem::MaybeUninit<T>]) {
+        let storage = ptr::slice_from_raw_parts(storage.as_ptr().cast::<T>(), storage.len());
+        self.buf.set(storage);
+        self.len = I::zeroed();
+    }
+
+    /// Returns the values.
+    /// ## Safety
+    /// The storage must still be where [`set_storage`](RelDynArray::set_storage)
+    /// put it, relative to `self`.
+    #[inline]
+    pub unsafe fn as_slice(&self) -> &[T] {
+        slice::from_raw_parts(self.buf.as_ptr().cast::<T>(), self.len())
+    }
+
+    /// Returns the values for writing.
+    /// ## Safety
+    /// See [`as_slice`](RelDynArray::as_slice).
+    #[inline]
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast::<T>(), len)
+    }
+
+    /// Appends `value`, or gives it back if the array is full.
+    /// ## Safety
+    /// See [`as_slice`](RelDynArray::as_slice).
+    pub unsafe fn push(&mut self, value: T) -> Result<(), T> {
+        let len = self.len();
+        if len == self.capacity() {
+            return Err(value);
+        }
+        self.buf.as_mut_ptr().cast::<T>().add(len).write(value);
+        self.len = I::from_isize(len as isize + 1).unwrap_unchecked();
+        Ok(())
+    }
+
+    /// Removes the last value and returns it.
+    /// ## Safety
+    /// See [`as_slice`](RelDynArray::as_slice).
+    pub unsafe fn pop(&mut self) -> Option<T> {
+        let len = self.len().checked_sub(1)?;
+        self.len = I::from_isize(len as isize).unwrap_unchecked();
+        Some(self.buf.as_ptr().cast::<T>().add(len).read())
+    }
+
+    /// Drops every value.
+    /// ## Safety
+    /// See [`as_slice`](RelDynArray::as_slice).
+    pub unsafe fn clear(&mut self) {
+        let values: *mut [T] = self.as_mut_slice();
+        self.len = I::zeroed();
+        ptr::drop_in_place(values);
+    }
+
+    /// Copies the values into a new [`DynArray`].
+    /// ## Safety
+    /// See [`as_slice`](RelDynArray::as_slice).
+    #[must_use]
+    pub unsafe fn to_dynarray(&self) -> DynArray<T> where T: Clone {
+        DynArray::from(self.as_slice())
+    }
+}
+
+impl<T, I: RelOffset> Default for RelDynArray<T, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, I: RelOffset> fmt::Debug for RelDynArray<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelDynArray").field("buf", &self.buf).field("len", &self.len).finish()
+    }
+}
+
+unsafe impl<T, I: RelOffset> Zeroable for RelDynArray<T, I> {}
+unsafe impl<T, I: RelOffset> FromBytes for RelDynArray<T, I> {}
+unsafe impl<T, I: RelOffset> NoUninit for RelDynArray<T, I> {}
+unsafe impl<T, I: RelOffset> Pod for RelDynArray<T, I> {}
@@ -0,0 +1,158 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Values stored in a fixed byte order.
+
+use core::{fmt, hash};
+
+use crate::{FromBytes, NoUninit, Pod, Zeroable};
+
+/// A number that can be converted between native and fixed byte order.
+pub trait Endian: Pod + Copy {
+    /// Converts a big endian value to native byte order.
+    fn from_be(value: Self) -> Self;
+    /// Converts a native value to big endian byte order.
+    fn to_be(self) -> Self;
+    /// Converts a little endian value to native byte order.
+    fn from_le(value: Self) -> Self;
+    /// Converts a native value to little endian byte order.
+    fn to_le(self) -> Self;
+}
+
+macro_rules! impl_endian_int {
+    ($($t:ty),*) => {
+        $(
+            impl Endian for $t {
+                #[inline]
+                fn from_be(value: Self) -> Self { <$t>::from_be(value) }
+                #[inline]
+                fn to_be(self) -> Self { <$t>::to_be(self) }
+                #[inline]
+                fn from_le(value: Self) -> Self { <$t>::from_le(value) }
+                #[inline]
+                fn to_le(self) -> Self { <$t>::to_le(self) }
+            }
+        )*
+    };
+}
+
+impl_endian_int! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize }
+
+macro_rules! impl_endian_float {
+    ($($t:ty),*) => {
+        $(
+            impl Endian for $t {
+                #[inline]
+                fn from_be(value: Self) -> Self { <$t>::from_bits(Endian::from_be(value.to_bits())) }
+                #[inline]
+                fn to_be(self) -> Self { <$t>::from_bits(Endian::to_be(self.to_bits())) }
+                #[inline]
+                fn from_le(value: Self) -> Self { <$t>::from_bits(Endian::from_le(value.to_bits())) }
+                #[inline]
+                fn to_le(self) -> Self { <$t>::from_bits(Endian::to_le(self.to_bits())) }
+            }
+        )*
+    };
+}
+
+impl_endian_float! { f32, f64 }
+
+macro_rules! endian_wrapper {
+    ($(#[$attr:meta])* $name:ident, $from:ident, $to:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct $name<T: Endian>(T);
+
+        impl<T: Endian> $name<T> {
+            /// Stores `value` in this byte order.
+            #[inline]
+            #[must_use]
+            pub fn new(value: T) -> Self {
+                Self(value.$to())
+            }
+
+            /// Returns the value in native byte order.
+            #[inline]
+            #[must_use]
+            pub fn get(self) -> T {
+                T::$from(self.0)
+            }
+
+            /// Replaces the value with `value`.
+            #[inline]
+            pub fn set(&mut self, value: T) {
+                self.0 = value.$to();
+            }
+
+            /// Returns the value as it is stored in memory.
+            #[inline]
+            #[must_use]
+            pub const fn raw(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T: Endian> From<T> for $name<T> {
+            #[inline]
+            fn from(value: T) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<T: Endian + fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        impl<T: Endian + hash::Hash> hash::Hash for $name<T> {
+            fn hash<H: hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state)
+            }
+        }
+
+        unsafe impl<T: Endian> Zeroable for $name<T> {}
+        unsafe impl<T: Endian> FromBytes for $name<T> {}
+        unsafe impl<T: Endian> NoUninit for $name<T> {}
+        unsafe impl<T: Endian> Pod for $name<T> {}
+    };
+}
+
+endian_wrapper! {
+    /// A value stored in big endian byte order.
+    ///
+    /// Has the same layout as `T`, so it can be used as a field of a
+    /// `#[derive(Pod)]` struct that mirrors a binary format.
+    BigEndian, from_be, to_be
+}
+
+endian_wrapper! {
+    /// A value stored in little endian byte order.
+    ///
+    /// Has the same layout as `T`, so it can be used as a field of a
+    /// `#[derive(Pod)]` struct that mirrors a binary format.
+    LittleEndian, from_le, to_le
+}
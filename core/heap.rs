@@ -0,0 +1,351 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! A free-list heap for memory handed over by the caller.
+
+use core::alloc::GlobalAlloc;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::fmt;
+
+use crate::{AllocError, Layout, RawAllocator, SpinLock, SpinLockGuard};
+
+/// A free block, stored in the block itself.
+struct Hole {
+    size: usize,
+    next: Option<NonNull<Hole>>,
+}
+
+/// How a [`Heap`] picks the free block to allocate from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FitStrategy {
+    /// Takes the first block that is large enough.
+    #[default]
+    FirstFit,
+    /// Takes the smallest block that is large enough.
+    BestFit,
+}
+
+/// A snapshot of how the memory of a [`Heap`] is used.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeapStats {
+    /// The number of bytes handed to the heap.
+    pub total: usize,
+    /// The number of bytes in allocated blocks, after rounding.
+    pub used: usize,
+    /// The number of bytes in free blocks.
+    pub free: usize,
+    /// The number of free blocks.
+    pub free_blocks: usize,
+    /// The size of the largest free block.
+    pub largest_free: usize,
+}
+
+impl HeapStats {
+    /// Returns how fragmented the free memory is, from `0.0` when it is a
+    /// single block to almost `1.0` when it is scattered in tiny blocks.
+    #[must_use]
+    pub fn fragmentation(&self) -> f64 {
+        if self.free == 0 {
+            return 0.0;
+        }
+        1.0 - self.largest_free as f64 / self.free as f64
+    }
+}
+
+/// A heap that keeps its free blocks in an address-ordered linked list.
+///
+/// Blocks are handed out in multiples of two words, and freed blocks are
+/// merged with their free neighbours. The heap can be given more memory
+/// with [`extend`](Heap::extend) at any time.
+pub struct Heap {
+    head: Option<NonNull<Hole>>,
+    strategy: FitStrategy,
+    total: usize,
+    used: usize,
+}
+
+unsafe impl Send for Heap {}
+
+impl Heap {
+    /// The granularity of blocks, which is also the smallest block.
+    const UNIT: usize = mem::size_of::<Hole>();
+
+    /// Creates a heap without memory.
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self { head: None, strategy: FitStrategy::FirstFit, total: 0, used: 0 }
+    }
+
+    /// Creates a heap that owns `memory`.
+    #[inline]
+    #[must_use]
+    pub fn from_slice(memory: &'static mut [u8]) -> Self {
+        let mut res = Self::empty();
+        res.extend_from_slice(memory);
+        res
+    }
+
+    /// Gives the heap the `size` bytes starting at address `start`.
+    ///
+    /// With its `memutils` feature, the `x86_64` crate also creates heaps
+    /// straight from a `VirtAddr` through its `HeapFromVirtAddr` trait.
+    /// ## Safety
+    /// The memory must be valid for reads and writes, unused by anything
+    /// else, and stay that way for as long as the heap lives.
+    #[inline]
+    pub unsafe fn init_from_addr(&mut self, start: usize, size: usize) {
+        self.extend(ptr::with_exposed_provenance_mut(start), size);
+    }
+
+    /// Gives the heap more memory.
+    #[inline]
+    pub fn extend_from_slice(&mut self, memory: &'static mut [u8]) {
+        unsafe { self.extend(memory.as_mut_ptr(), memory.len()) }
+    }
+
+    /// Gives the heap the `size` bytes starting at `start`.
+    ///
+    /// The region may be anywhere, including right after memory the heap
+    /// already has; bytes that do not fit the block granularity are ignored.
+    /// ## Safety
+    /// The memory must be valid for reads and writes, unused by anything
+    /// else, and stay that way for as long as the heap lives.
+    pub unsafe fn extend(&mut self, start: *mut u8, size: usize) {
+        let end = start.addr().saturating_add(size);
+        let aligned = start.addr().next_multiple_of(Self::UNIT);
+        if aligned >= end {
+            return;
+        }
+        let size = (end - aligned) / Self::UNIT * Self::UNIT;
+        if size == 0 {
+            return;
+        }
+        self.total += size;
+        self.insert_hole(start.wrapping_add(aligned - start.addr()), size);
+    }
+
+    /// Sets how free blocks are picked.
+    #[inline]
+    pub fn set_strategy(&mut self, strategy: FitStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// Returns how free blocks are picked.
+    #[inline]
+    #[must_use]
+    pub fn strategy(&self) -> FitStrategy {
+        self.strategy
+    }
+
+    /// Returns usage and fragmentation statistics. Walks the free list.
+    #[must_use]
+    pub fn stats(&self) -> HeapStats {
+        let mut stats = HeapStats { total: self.total, used: self.used, ..HeapStats::default() };
+        let mut current = self.head;
+        while let Some(hole) = current {
+            let hole = unsafe { hole.as_ref() };
+            stats.free += hole.size;
+            stats.free_blocks += 1;
+            stats.largest_free = stats.largest_free.max(hole.size);
+            current = hole.next;
+        }
+        stats
+    }
+
+    /// Allocates a block that fits `layout`.
+    pub fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let (size, align) = Self::block_layout(layout).ok_or(AllocError::CapacityOverflow)?;
+        let mut best: Option<(Option<NonNull<Hole>>, NonNull<Hole>, usize)> = None;
+        let mut prev = None;
+        let mut current = self.head;
+        while let Some(hole) = current {
+            let (hole_size, next) = unsafe { (hole.as_ref().size, hole.as_ref().next) };
+            let start = hole.addr().get().next_multiple_of(align);
+            let fits = start.checked_add(size).is_some_and(|end| end <= hole.addr().get() + hole_size);
+            if fits {
+                let better = match best {
+                    Some((_, best_hole, _)) => unsafe { hole_size < best_hole.as_ref().size },
+                    None => true,
+                };
+                if better {
+                    best = Some((prev, hole, start));
+                }
+                if self.strategy == FitStrategy::FirstFit || hole_size == size {
+                    break;
+                }
+            }
+            prev = current;
+            current = next;
+        }
+        let (prev, hole, start) = best.ok_or(AllocError::OutOfMemory(layout))?;
+        unsafe { self.carve(prev, hole, start, size) };
+        self.used += size;
+        Ok(unsafe { NonNull::new_unchecked(hole.as_ptr().cast::<u8>().wrapping_add(start - hole.addr().get())) })
+    }
+
+    /// Frees a block.
+    /// ## Safety
+    /// `ptr` must have been returned by [`allocate`](Heap::allocate) on this heap for `layout`.
+    pub unsafe fn deallocate(&mut self, ptr: NonNull<u8>, layout: Layout) {
+        let (size, _) = Self::block_layout(layout).unwrap_unchecked();
+        self.used -= size;
+        self.insert_hole(ptr.as_ptr(), size);
+    }
+
+    /// Returns the size and alignment of the block used for `layout`.
+    #[inline]
+    fn block_layout(layout: Layout) -> Option<(usize, usize)> {
+        let size = layout.size().max(1).checked_next_multiple_of(Self::UNIT)?;
+        Some((size, layout.align().max(Self::UNIT)))
+    }
+
+    /// Takes `size` bytes at address `start` out of `hole`, whose predecessor is `prev`.
+    unsafe fn carve(&mut self, prev: Option<NonNull<Hole>>, hole: NonNull<Hole>, start: usize, size: usize) {
+        let Hole { size: hole_size, next } = hole.read();
+        let base = hole.as_ptr().cast::<u8>();
+        let front = start - hole.addr().get();
+        let back = hole_size - front - size;
+        let mut next = next;
+        if back > 0 {
+            let back_hole = base.wrapping_add(front + size).cast::<Hole>();
+            back_hole.write(Hole { size: back, next });
+            next = Some(NonNull::new_unchecked(back_hole));
+        }
+        if front > 0 {
+            hole.write(Hole { size: front, next });
+        } else {
+            self.set_next(prev, next);
+        }
+    }
+
+    /// Adds a free block of `size` bytes at `start`, merging it with its neighbours.
+    unsafe fn insert_hole(&mut self, start: *mut u8, size: usize) {
+        let mut prev: Option<NonNull<Hole>> = None;
+        let mut current = self.head;
+        while let Some(hole) = current {
+            if hole.addr().get() > start.addr() {
+                break;
+            }
+            prev = current;
+            current = hole.as_ref().next;
+        }
+        let mut block = NonNull::new_unchecked(start.cast::<Hole>());
+        block.write(Hole { size, next: current });
+        if let Some(next) = current {
+            if start.addr() + size == next.addr().get() {
+                let next = next.read();
+                block.as_mut().size += next.size;
+                block.as_mut().next = next.next;
+            }
+        }
+        match prev {
+            Some(mut prev) if prev.addr().get() + prev.as_ref().size == start.addr() => {
+                let block = block.read();
+                prev.as_mut().size += block.size;
+                prev.as_mut().next = block.next;
+            }
+            _ => self.set_next(prev, Some(block)),
+        }
+    }
+
+    /// Points `prev`, or the head of the list, at `next`.
+    #[inline]
+    unsafe fn set_next(&mut self, prev: Option<NonNull<Hole>>, next: Option<NonNull<Hole>>) {
+        match prev {
+            Some(mut prev) => prev.as_mut().next = next,
+            None => self.head = next,
+        }
+    }
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for Heap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Heap").field("strategy", &self.strategy).field("stats", &self.stats()).finish()
+    }
+}
+
+/// A [`Heap`] behind a [`SpinLock`], usable as the `#[global_allocator]`.
+///
+/// ## Example
+/// ```rust,ignore
+/// #[global_allocator]
+/// static HEAP: LockedHeap = LockedHeap::empty();
+///
+/// unsafe { HEAP.lock().init_from_addr(HEAP_START, HEAP_SIZE) };
+/// ```
+#[derive(Debug, Default)]
+pub struct LockedHeap(SpinLock<Heap>);
+
+impl LockedHeap {
+    /// Creates a locked heap without memory.
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(SpinLock::new(Heap::empty()))
+    }
+
+    /// Creates a locked heap that owns `memory`.
+    #[inline]
+    #[must_use]
+    pub fn from_slice(memory: &'static mut [u8]) -> Self {
+        Self(SpinLock::new(Heap::from_slice(memory)))
+    }
+
+    /// Locks the heap, to extend it or read its statistics.
+    #[inline]
+    pub fn lock(&self) -> SpinLockGuard<'_, Heap> {
+        self.0.lock()
+    }
+}
+
+unsafe impl GlobalAlloc for LockedHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.lock().allocate(layout).map_or(ptr::null_mut(), NonNull::as_ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.lock().deallocate(NonNull::new_unchecked(ptr), layout)
+    }
+}
+
+unsafe impl RawAllocator for LockedHeap {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        unsafe { GlobalAlloc::alloc(self, layout) }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        GlobalAlloc::dealloc(self, ptr, layout)
+    }
+}
@@ -0,0 +1,230 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Hexadecimal views of bytes.
+
+use core::{fmt, mem};
+
+use crate::{FieldLayout, MemLayout, PaddingHole};
+
+/// A display adapter that formats bytes as a hexdump.
+///
+/// `{}` prints one row per line: the offset, the bytes in hex, and the
+/// printable ones as ASCII. `{:x}` and `{:X}` print the bytes as a single
+/// hex string instead, prefixed with `0x` when the alternate flag is set.
+/// `{:?}` prints the bytes in hex between brackets, and `{:#?}` prints the full hexdump.
+///
+/// ```text
+/// 00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a       |Hello, world!.|
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    base: Option<usize>,
+    width: usize,
+    group: usize,
+}
+
+impl<'a> HexDump<'a> {
+    /// Creates a hexdump of `bytes` with 16 bytes per row, grouped by 8.
+    #[inline]
+    #[must_use]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, base: None, width: 16, group: 8 }
+    }
+
+    /// Sets the number of bytes per row.
+    /// ## Panics
+    /// Panics if `width` is not 8, 16 or 32.
+    #[inline]
+    #[must_use]
+    pub const fn width(mut self, width: usize) -> Self {
+        assert!(width == 8 || width == 16 || width == 32, "width must be 8, 16 or 32");
+        self.width = width;
+        self
+    }
+
+    /// Sets how many bytes are grouped together before an extra space.
+    ///
+    /// A group as large as the row disables grouping.
+    /// ## Panics
+    /// Panics if `group` is zero.
+    #[inline]
+    #[must_use]
+    pub const fn group(mut self, group: usize) -> Self {
+        assert!(group != 0, "group must not be zero");
+        self.group = group;
+        self
+    }
+
+    /// Labels rows with addresses starting at `base` instead of offsets.
+    #[inline]
+    #[must_use]
+    pub const fn base(mut self, base: usize) -> Self {
+        self.base = Some(base);
+        self
+    }
+
+    /// Returns the bytes being dumped.
+    #[inline]
+    #[must_use]
+    pub const fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Writes one row starting at `offset`.
+    fn row(&self, f: &mut fmt::Formatter<'_>, offset: usize, row: &[u8]) -> fmt::Result {
+        match self.base {
+            Some(base) => write!(f, "{:01$x}", base.wrapping_add(offset), mem::size_of::<usize>() * 2)?,
+            None => write!(f, "{:08x}", offset)?,
+        }
+        f.write_str(" ")?;
+        for i in 0..self.width {
+            if i % self.group == 0 {
+                f.write_str(" ")?;
+            }
+            match row.get(i) {
+                Some(b) => write!(f, "{:02x} ", b)?,
+                None => f.write_str("   ")?,
+            }
+        }
+        f.write_str("|")?;
+        for &b in row {
+            let c = if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' };
+            fmt::Write::write_char(f, c)?;
+        }
+        f.write_str("|")
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.bytes.chunks(self.width).enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+            self.row(f, i * self.width, row)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return fmt::Display::fmt(self, f);
+        }
+        f.write_str("[")?;
+        for (i, b) in self.bytes.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl fmt::LowerHex for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+impl fmt::UpperHex for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.bytes.iter().try_for_each(|b| write!(f, "{:02X}", b))
+    }
+}
+
+/// A display adapter that prints the bytes of a struct field by field.
+///
+/// Each line shows the offset, the name and type of the field, and its
+/// bytes in hex. Padding holes are shown as `<padding>`.
+///
+/// ```text
+/// 0000  kind: u8     = 01
+/// 0001  <padding>    = 00 00 00 00 00 00 00
+/// 0008  address: u64 = 00 10 00 00 00 00 00 00
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDump<'a> {
+    bytes: &'a [u8],
+    fields: &'static [FieldLayout],
+    padding: &'static [PaddingHole],
+}
+
+impl<'a> FieldDump<'a> {
+    /// Creates a field dump of the bytes of a `T`.
+    /// ## Panics
+    /// Panics if `bytes` is not exactly `size_of::<T>()` long.
+    #[must_use]
+    pub fn new<T: MemLayout>(bytes: &'a [u8]) -> Self {
+        assert_eq!(bytes.len(), mem::size_of::<T>(), "bytes must have the size of the type");
+        Self { bytes, fields: T::FIELDS, padding: T::PADDING }
+    }
+}
+
+const PADDING_LABEL: &str = "<padding>";
+
+impl fmt::Display for FieldDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.fields.iter()
+            .map(|field| field.name.len() + 2 + field.ty.len())
+            .fold(PADDING_LABEL.len(), core::cmp::max);
+        let mut offset = 0;
+        while offset < self.bytes.len() {
+            let field = self.fields.iter().find(|field| field.offset == offset && field.size != 0);
+            let (len, label_len) = match field {
+                Some(field) => {
+                    write!(f, "{:04x}  {}: {}", offset, field.name, field.ty)?;
+                    (field.size, field.name.len() + 2 + field.ty.len())
+                }
+                None => {
+                    let len = self.padding.iter()
+                        .find(|hole| hole.offset == offset)
+                        .map_or(self.bytes.len() - offset, |hole| hole.size);
+                    write!(f, "{:04x}  {}", offset, PADDING_LABEL)?;
+                    (len, PADDING_LABEL.len())
+                }
+            };
+            write!(f, "{:1$} =", "", width - label_len)?;
+            for b in &self.bytes[offset..offset + len] {
+                write!(f, " {:02x}", b)?;
+            }
+            offset += len;
+            if offset < self.bytes.len() {
+                f.write_str("\n")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,10 +3,13 @@ use core::slice;
 use core::ptr;
 use core::mem;
 use core::fmt;
+use core::hash;
+use core::ops;
+use core::pin;
 
 use crate::*;
 
-impl<'a,T> ByteObject<'a,T> {
+impl<'a,T: ?Sized> ByteObject<'a,T> {
     /// Creates a new `ByteObject` from a slice of bytes and a length
     #[inline]
     #[must_use]
@@ -71,6 +74,49 @@ impl<'a,T> ByteObject<'a,T> {
         self.bytes
     }
 
+    /// returns a `ByteReader` over the bytes of the `ByteObject`
+    #[inline]
+    #[must_use]
+    pub const fn reader(&self) -> ByteReader<'a> {
+        ByteReader::new(self.bytes)
+    }
+
+    /// returns a `HexDump` of the bytes, labelled with their addresses
+    #[inline]
+    #[must_use]
+    pub fn hexdump(&self) -> HexDump<'a> {
+        HexDump::new(self.bytes).base(self.addr.addr())
+    }
+
+    /// returns the bytes that differ between this `ByteObject` and `other`
+    /// ## Panics
+    /// Panics if the two have different lengths, as unsized objects may.
+    #[inline]
+    #[must_use]
+    pub fn diff<'b>(&self, other: &ByteObject<'b, T>) -> ByteDiff<'b> where 'a: 'b {
+        ByteDiff::new(self.bytes, other.bytes)
+    }
+
+    /// returns the pointer metadata of the object:
+    /// `()` for sized types, the length for slices and `str`,
+    /// and the vtable for trait objects
+    #[inline]
+    #[must_use]
+    pub fn metadata(&self) -> <T as ptr::Pointee>::Metadata {
+        ptr::metadata(self.addr)
+    }
+}
+
+impl<'a,T: MemLayout> ByteObject<'a,T> {
+    /// returns a `FieldDump` of the bytes, one line per field
+    #[inline]
+    #[must_use]
+    pub fn field_dump(&self) -> FieldDump<'a> {
+        FieldDump::new::<T>(self.bytes)
+    }
+}
+
+impl<'a,T> ByteObject<'a,T> {
     /// returns a clone of the bytes of the `ByteObject`
     /// ## Safety
     /// This function is unsafe because it can cause a memory leak
@@ -80,28 +126,117 @@ impl<'a,T> ByteObject<'a,T> {
     #[inline]
     #[must_use]
     pub unsafe fn as_object(&self) -> T {
-        let res = create_object_byteguard_from_pointer(self.addr);
+        self.as_object_in(Global)
+    }
+
+    /// returns a clone of the bytes of the `ByteObject`,
+    /// using `alloc` for the intermediate copy
+    /// ## Safety
+    /// See [`ByteObject::as_object`].
+    #[inline]
+    #[must_use]
+    pub unsafe fn as_object_in<A: RawAllocator>(&self, alloc: A) -> T {
+        let res = create_object_byteguard_from_pointer_in(self.addr, alloc);
         res.into_inner()
     }
+
+    /// returns a clone of the bytes of the `ByteObject`,
+    /// or an error if the intermediate copy could not be allocated
+    /// ## Safety
+    /// See [`ByteObject::as_object`].
+    #[inline]
+    pub unsafe fn try_as_object(&self) -> Result<T, AllocError> {
+        self.try_as_object_in(Global)
+    }
+
+    /// returns a clone of the bytes of the `ByteObject`, using `alloc` for the
+    /// intermediate copy, or an error if it could not be allocated
+    /// ## Safety
+    /// See [`ByteObject::as_object`].
+    #[inline]
+    pub unsafe fn try_as_object_in<A: RawAllocator>(&self, alloc: A) -> Result<T, AllocError> {
+        let res = try_create_object_byteguard_from_pointer_in(self.addr, alloc)?;
+        Ok(res.into_inner())
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for ByteObject<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteObject")
+            .field("addr", &self.addr)
+            .field("length", &self.length)
+            .field("bytes", &self.hexdump())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> fmt::LowerHex for ByteObject<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&HexDump::new(self.bytes), f)
+    }
+}
+
+impl<T: ?Sized> fmt::UpperHex for ByteObject<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&HexDump::new(self.bytes), f)
+    }
+}
+
+impl<T: ?Sized> Clone for ByteObject<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for ByteObject<'_, T> {}
+
+impl<T: ?Sized> PartialEq for ByteObject<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && ptr::eq(self.addr, other.addr)
+    }
 }
 
-impl<'a,T> From<&T> for ByteObject<'a,T> {
+impl<T: ?Sized> Eq for ByteObject<'_, T> {}
+
+impl<T: ?Sized> PartialOrd for ByteObject<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for ByteObject<'_, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.bytes.cmp(other.bytes)
+            .then_with(|| self.addr.cast::<u8>().cmp(&other.addr.cast::<u8>()))
+            .then_with(|| self.metadata().cmp(&other.metadata()))
+    }
+}
+
+impl<T: ?Sized> hash::Hash for ByteObject<'_, T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+        self.addr.cast::<u8>().hash(state);
+        self.metadata().hash(state);
+    }
+}
+
+impl<'a,T: ?Sized> From<&T> for ByteObject<'a,T> {
     fn from(t: &T) -> Self {
-        let addr: *const T = &*t;
+        let addr: *const T = t;
         let bytes: &'a [u8] = unsafe {
-            slice::from_raw_parts(addr as *const u8, mem::size_of::<T>())
+            slice::from_raw_parts(addr as *const u8, mem::size_of_val(t))
         };
         Self::from_raw_parts(bytes.len(), bytes, addr)
     }
 }
 
-impl<'a,T> From<&mut T> for ByteObject<'a,T> {
+impl<'a,T: ?Sized> From<&mut T> for ByteObject<'a,T> {
     fn from(t: &mut T) -> Self {
         ByteObject::from(&*t)
     }
 }
 
-impl<'a,T> From<*mut T> for ByteObject<'a,T> {
+impl<'a,T: ?Sized> From<*mut T> for ByteObject<'a,T> {
     fn from(t: *mut T) -> Self {
         unsafe {
             ByteObject::from(&*t)
@@ -109,15 +244,14 @@ impl<'a,T> From<*mut T> for ByteObject<'a,T> {
     }
 }
 
-impl<'a,T> From<*const T> for ByteObject<'a,T> {
+impl<'a,T: ?Sized> From<*const T> for ByteObject<'a,T> {
     fn from(t: *const T) -> Self {
         unsafe {
             ByteObject::from(&*t)
         }
     }
 }
-
-impl<T> fmt::Debug for ByteGuard<T> {
+impl<T: ?Sized, A: RawAllocator> fmt::Debug for ByteGuard<T, A> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ByteGuard")
             .field("ptr", &self.ptr)
@@ -126,65 +260,538 @@ impl<T> fmt::Debug for ByteGuard<T> {
     }
 }
 
+/// Allocates a block for `layout` in `alloc`.
+#[inline]
+fn allocate_block<A: RawAllocator>(layout: Layout, alloc: &A, zeroed: bool) -> Result<ptr::NonNull<u8>, AllocError> {
+    let ptr = if zeroed { alloc.allocate_zeroed(layout) } else { alloc.allocate(layout) };
+    ptr::NonNull::new(ptr).ok_or(AllocError::OutOfMemory(layout))
+}
+
 impl<T> ByteGuard<T> {
-    /// Creates a new `ByteGuard` from a pointer to a type.
+    /// Moves `value` into a new allocation.
+    #[inline]
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self::new_in(value, Global)
+    }
+
+    /// Moves `value` into a new allocation,
+    /// returning an error if the allocation fails.
     #[inline]
-    #[must_use = "this returns the result of the operation, without modifying the original"]
-    pub(crate) fn new(ptr: *mut u8, layout: crate::Layout) -> Self {
-        if ptr.is_null() {
-            crate::handle_alloc_error(layout);
+    pub fn try_new(value: T) -> Result<Self, AllocError> {
+        Self::try_new_in(value, Global)
+    }
+
+    /// Moves `value` into a new allocation and pins it there.
+    #[inline]
+    #[must_use]
+    pub fn pin(value: T) -> pin::Pin<Self> {
+        Self::new(value).into_pin()
+    }
+
+    /// Allocates room for a `T` without initializing it.
+    #[inline]
+    #[must_use]
+    pub fn new_uninit() -> ByteGuard<mem::MaybeUninit<T>> {
+        Self::new_uninit_in(Global)
+    }
+
+    /// Allocates room for a `T`, filled with zeroes.
+    #[inline]
+    #[must_use]
+    pub fn new_zeroed() -> ByteGuard<mem::MaybeUninit<T>> {
+        Self::new_zeroed_in(Global)
+    }
+}
+
+impl<T, A: RawAllocator> ByteGuard<T, A> {
+    /// Moves `value` into a new allocation in `alloc`.
+    #[inline]
+    #[must_use]
+    pub fn new_in(value: T, alloc: A) -> Self {
+        Self::try_new_in(value, alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Moves `value` into a new allocation in `alloc` and pins it there.
+    #[inline]
+    #[must_use]
+    pub fn pin_in(value: T, alloc: A) -> pin::Pin<Self> {
+        Self::new_in(value, alloc).into_pin()
+    }
+
+    /// Pins the guarded value, which stays at its address until the guard is dropped.
+    #[inline]
+    #[must_use]
+    pub fn into_pin(self) -> pin::Pin<Self> {
+        unsafe { pin::Pin::new_unchecked(self) }
+    }
+
+    /// Moves `value` into a new allocation in `alloc`,
+    /// returning an error if the allocation fails.
+    #[inline]
+    pub fn try_new_in(value: T, alloc: A) -> Result<Self, AllocError> {
+        Ok(Self::try_new_uninit_in(alloc)?.write(value))
+    }
+
+    /// Allocates room for a `T` in `alloc` without initializing it.
+    #[inline]
+    #[must_use]
+    pub fn new_uninit_in(alloc: A) -> ByteGuard<mem::MaybeUninit<T>, A> {
+        Self::try_new_uninit_in(alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Allocates room for a `T` in `alloc` without initializing it,
+    /// returning an error if the allocation fails.
+    #[inline]
+    pub fn try_new_uninit_in(alloc: A) -> Result<ByteGuard<mem::MaybeUninit<T>, A>, AllocError> {
+        let layout = Layout::new::<T>();
+        let ptr = allocate_block(layout, &alloc, false)?;
+        Ok(ByteGuard { ptr: ptr.cast(), layout, alloc, _marker: marker::PhantomData })
+    }
+
+    /// Allocates room for a `T` in `alloc`, filled with zeroes.
+    #[inline]
+    #[must_use]
+    pub fn new_zeroed_in(alloc: A) -> ByteGuard<mem::MaybeUninit<T>, A> {
+        Self::try_new_zeroed_in(alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Allocates room for a `T` in `alloc`, filled with zeroes,
+    /// returning an error if the allocation fails.
+    #[inline]
+    pub fn try_new_zeroed_in(alloc: A) -> Result<ByteGuard<mem::MaybeUninit<T>, A>, AllocError> {
+        let layout = Layout::new::<T>();
+        let ptr = allocate_block(layout, &alloc, true)?;
+        Ok(ByteGuard { ptr: ptr.cast(), layout, alloc, _marker: marker::PhantomData })
+    }
+
+    /// Returns the guarded value.
+    /// Consumes the guard.
+    #[inline]
+    #[must_use = "if you don't use the result, the value will be dropped immediately"]
+    pub fn into_inner(self) -> T {
+        let (ptr, layout, alloc) = self.into_parts();
+        unsafe {
+            let res: T = ptr::read(ptr);
+            alloc.deallocate(ptr as *mut u8, layout);
+            res
         }
-        Self { ptr, layout, _marker: marker::PhantomData }
+    }
+}
+
+impl<T> ByteGuard<[T]> {
+    /// Allocates room for `len` values without initializing them.
+    #[inline]
+    #[must_use]
+    pub fn new_uninit_slice(len: usize) -> ByteGuard<[mem::MaybeUninit<T>]> {
+        Self::new_uninit_slice_in(len, Global)
+    }
+
+    /// Allocates room for `len` values, filled with zeroes.
+    #[inline]
+    #[must_use]
+    pub fn new_zeroed_slice(len: usize) -> ByteGuard<[mem::MaybeUninit<T>]> {
+        Self::new_zeroed_slice_in(len, Global)
+    }
+
+    /// Clones every value of `values` into a new allocation.
+    #[inline]
+    #[must_use]
+    pub fn from_slice(values: &[T]) -> Self where T: Clone {
+        Self::from_slice_in(values, Global)
+    }
+}
+
+impl<T, A: RawAllocator> ByteGuard<[T], A> {
+    /// Allocates room for `len` values in `alloc` without initializing them.
+    #[inline]
+    #[must_use]
+    pub fn new_uninit_slice_in(len: usize, alloc: A) -> ByteGuard<[mem::MaybeUninit<T>], A> {
+        Self::try_new_uninit_slice_in(len, alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Allocates room for `len` values in `alloc` without initializing them,
+    /// returning an error if the allocation fails.
+    pub fn try_new_uninit_slice_in(len: usize, alloc: A) -> Result<ByteGuard<[mem::MaybeUninit<T>], A>, AllocError> {
+        Self::allocate_slice_in(len, alloc, false)
+    }
+
+    /// Allocates room for `len` values in `alloc`, filled with zeroes.
+    #[inline]
+    #[must_use]
+    pub fn new_zeroed_slice_in(len: usize, alloc: A) -> ByteGuard<[mem::MaybeUninit<T>], A> {
+        Self::try_new_zeroed_slice_in(len, alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Allocates room for `len` values in `alloc`, filled with zeroes,
+    /// returning an error if the allocation fails.
+    pub fn try_new_zeroed_slice_in(len: usize, alloc: A) -> Result<ByteGuard<[mem::MaybeUninit<T>], A>, AllocError> {
+        Self::allocate_slice_in(len, alloc, true)
+    }
+
+    fn allocate_slice_in(len: usize, alloc: A, zeroed: bool) -> Result<ByteGuard<[mem::MaybeUninit<T>], A>, AllocError> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError::CapacityOverflow)?;
+        let ptr = allocate_block(layout, &alloc, zeroed)?;
+        Ok(ByteGuard {
+            ptr: ptr::NonNull::slice_from_raw_parts(ptr.cast(), len),
+            layout,
+            alloc,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// Clones every value of `values` into a new allocation in `alloc`.
+    #[inline]
+    #[must_use]
+    pub fn from_slice_in(values: &[T], alloc: A) -> Self where T: Clone {
+        Self::try_from_slice_in(values, alloc).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Clones every value of `values` into a new allocation in `alloc`,
+    /// returning an error if the allocation fails.
+    pub fn try_from_slice_in(values: &[T], alloc: A) -> Result<Self, AllocError> where T: Clone {
+        let mut res = Self::try_new_uninit_slice_in(values.len(), alloc)?;
+        for (slot, value) in res.iter_mut().zip(values) {
+            slot.write(value.clone());
+        }
+        Ok(unsafe { res.assume_init() })
+    }
+
+    /// Resizes the allocation in place to hold `len` values,
+    /// filling new slots with clones of `value`.
+    #[inline]
+    pub fn resize(&mut self, len: usize, value: T) where T: Clone {
+        self.try_resize(len, value).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Resizes the allocation in place to hold `len` values,
+    /// filling new slots with clones of `value`,
+    /// returning an error if the allocation fails.
+    ///
+    /// On failure the guard is left unchanged.
+    pub fn try_resize(&mut self, len: usize, value: T) -> Result<(), AllocError> where T: Clone {
+        let old_len = self.len();
+        let old = self.ptr.as_ptr() as *mut T;
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError::CapacityOverflow)?;
+        let mut base = old;
+        // Values past `len` must be dropped before a shrink loses them, which
+        // could not be undone if it then failed. They are moved to a new block instead.
+        let moved = len < old_len && mem::needs_drop::<T>() && layout.size() != self.layout.size();
+        if layout.size() != self.layout.size() {
+            let raw = unsafe {
+                if layout.size() > self.layout.size() {
+                    self.alloc.grow(old as *mut u8, self.layout, layout)
+                } else if moved {
+                    let raw = self.alloc.allocate(layout);
+                    if !raw.is_null() {
+                        ptr::copy_nonoverlapping(old, raw as *mut T, len);
+                    }
+                    raw
+                } else {
+                    self.alloc.shrink(old as *mut u8, self.layout, layout)
+                }
+            };
+            if raw.is_null() {
+                return Err(AllocError::OutOfMemory(layout));
+            }
+            base = raw as *mut T;
+        }
+        let old_layout = mem::replace(&mut self.layout, layout);
+        unsafe {
+            self.ptr = ptr::NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(base, core::cmp::min(len, old_len)));
+            if len < old_len && mem::needs_drop::<T>() {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(old.add(len), old_len - len));
+            }
+            if moved {
+                self.alloc.deallocate(old as *mut u8, old_layout);
+            }
+        }
+        // The length grows one value at a time, so a panicking `clone`
+        // never leaves uninitialized values behind the slice.
+        for i in old_len..len {
+            unsafe {
+                ptr::write(base.add(i), value.clone());
+                self.ptr = ptr::NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(base, i + 1));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, A: RawAllocator> ByteGuard<mem::MaybeUninit<T>, A> {
+    /// Initializes the guarded value with `value`.
+    #[inline]
+    pub fn write(mut self, value: T) -> ByteGuard<T, A> {
+        (*self).write(value);
+        unsafe { self.assume_init() }
+    }
+
+    /// Converts to `ByteGuard<T, A>`.
+    /// ## Safety
+    /// The guarded value must be initialized.
+    #[inline]
+    pub unsafe fn assume_init(self) -> ByteGuard<T, A> {
+        let (ptr, layout, alloc) = self.into_parts();
+        ByteGuard::from_parts(ptr as *mut T, layout, alloc)
+    }
+}
+
+impl<T, A: RawAllocator> ByteGuard<[mem::MaybeUninit<T>], A> {
+    /// Converts to `ByteGuard<[T], A>`.
+    /// ## Safety
+    /// Every guarded value must be initialized.
+    #[inline]
+    pub unsafe fn assume_init(self) -> ByteGuard<[T], A> {
+        let (ptr, layout, alloc) = self.into_parts();
+        ByteGuard::from_parts(ptr as *mut [T], layout, alloc)
+    }
+}
+
+impl<T: ?Sized> ByteGuard<T> {
+    /// Takes ownership of a value allocated with the global allocator.
+    /// ## Safety
+    /// `ptr` must have been returned by [`ByteGuard::into_raw`] on a guard
+    /// using the global allocator, and must not be used afterwards.
+    #[inline]
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        Self::from_raw_in(ptr, Global)
+    }
+}
+
+impl<T: ?Sized, A: RawAllocator> ByteGuard<T, A> {
+    /// Takes ownership of a value allocated in `alloc`.
+    /// ## Safety
+    /// `ptr` must have been returned by [`ByteGuard::into_raw`] on a guard
+    /// using `alloc`, and must not be used afterwards.
+    #[inline]
+    pub unsafe fn from_raw_in(ptr: *mut T, alloc: A) -> Self {
+        let layout = Layout::for_value(&*ptr);
+        Self::from_parts(ptr, layout, alloc)
+    }
+
+    #[inline]
+    unsafe fn from_parts(ptr: *mut T, layout: Layout, alloc: A) -> Self {
+        Self { ptr: ptr::NonNull::new_unchecked(ptr), layout, alloc, _marker: marker::PhantomData }
+    }
+
+    #[inline]
+    fn into_parts(self) -> (*mut T, Layout, A) {
+        let this = mem::ManuallyDrop::new(self);
+        (this.ptr.as_ptr(), this.layout, unsafe { ptr::read(&this.alloc) })
+    }
+
+    /// Gives up ownership of the guarded value without dropping it.
+    ///
+    /// The allocator is dropped; use [`ByteGuard::into_raw_with_allocator`] to keep it.
+    #[inline]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw(self) -> *mut T {
+        self.into_raw_with_allocator().0
+    }
+
+    /// Gives up ownership of the guarded value without dropping it,
+    /// returning the allocator it lives in.
+    #[inline]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw_with_allocator(self) -> (*mut T, A) {
+        let (ptr, _, alloc) = self.into_parts();
+        (ptr, alloc)
+    }
+
+    /// Leaks the guarded value, returning a reference that lives as long as the allocator.
+    #[inline]
+    pub fn leak<'a>(self) -> &'a mut T where A: 'a {
+        let (ptr, _, alloc) = self.into_parts();
+        mem::forget(alloc);
+        unsafe { &mut *ptr }
+    }
+
+    /// Returns the allocator the guarded value lives in.
+    #[inline]
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns the layout of the allocation.
+    #[inline]
+    #[must_use]
+    pub fn layout(&self) -> Layout {
+        self.layout
     }
 
     /// Returns a pointer to the guarded value.
     #[inline]
     #[must_use = "if you don't use the result, the value will be dropped immediately"]
     pub fn as_ptr(&self) -> *const T {
-        self.ptr as *const T
+        self.ptr.as_ptr()
     }
 
     /// Returns a mutable pointer to the guarded value.
     #[inline]
     #[must_use = "if you don't use the result, the value will be dropped immediately"]
     pub fn as_mut_ptr(&mut self) -> *mut T {
-        self.ptr as *mut T
+        self.ptr.as_ptr()
     }
+}
 
-    /// Returns the guarded value.
-    /// Consumes the guard.
+impl<T: ?Sized + AsBytes, A: RawAllocator> ByteGuard<T, A> {
+    /// Returns a `ByteReader` over the bytes of the guarded value.
     #[inline]
-    #[must_use = "if you don't use the result, the value will be dropped immediately"]
-    pub unsafe fn into_inner(self) -> T {
-        let res: T = ptr::read(self.as_ptr());
-        drop(self);
-        res
+    #[must_use]
+    pub fn reader(&self) -> ByteReader<'_> {
+        ByteReader::new(self.as_bytes())
     }
+}
 
-    /// Copies the value from the given pointer to the guarded value.
-    /// ## Safety
-    /// This function is unsafe because it can cause a memory leak
-    /// if the returned value is not freed.
+impl<T: FromBytes + NoUninit, A: RawAllocator> ByteGuard<T, A> {
+    /// Returns a `ByteWriter` over the bytes of the guarded value.
+    #[inline]
+    #[must_use]
+    pub fn writer(&mut self) -> ByteWriter<'_> {
+        ByteWriter::new(as_bytes_mut(&mut **self))
+    }
+}
+
+impl<T: FromBytes + NoUninit, A: RawAllocator> ByteGuard<[T], A> {
+    /// Returns a `ByteWriter` over the bytes of the guarded slice.
     #[inline]
-    pub(crate) unsafe fn copy_from(&mut self, src: *const T) {
-        ptr::copy_nonoverlapping(src, self.as_mut_ptr(), 1);
+    #[must_use]
+    pub fn writer(&mut self) -> ByteWriter<'_> {
+        ByteWriter::new(slice_as_bytes_mut(self))
     }
 }
 
-impl<T> AsRef<T> for ByteGuard<T> {
+unsafe impl<T: ?Sized + Send, A: RawAllocator + Send> Send for ByteGuard<T, A> {}
+unsafe impl<T: ?Sized + Sync, A: RawAllocator + Sync> Sync for ByteGuard<T, A> {}
+
+impl<T: ?Sized, A: RawAllocator> ops::Deref for ByteGuard<T, A> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized, A: RawAllocator> ops::DerefMut for ByteGuard<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized, A: RawAllocator> AsRef<T> for ByteGuard<T, A> {
     fn as_ref(&self) -> &T {
-        unsafe { &*self.as_ptr() }
+        self
     }
 }
 
-impl<T> AsMut<T> for ByteGuard<T> {
+impl<T: ?Sized, A: RawAllocator> AsMut<T> for ByteGuard<T, A> {
     fn as_mut(&mut self) -> &mut T {
-        unsafe { &mut *self.as_mut_ptr() }
+        self
+    }
+}
+
+impl<T: Clone, A: RawAllocator + Clone> Clone for ByteGuard<T, A> {
+    /// Clones the guarded value into a new allocation.
+    fn clone(&self) -> Self {
+        Self::new_in((**self).clone(), self.alloc.clone())
+    }
+}
+
+impl<T: Clone, A: RawAllocator + Clone> Clone for ByteGuard<[T], A> {
+    /// Clones the guarded values into a new allocation.
+    fn clone(&self) -> Self {
+        Self::from_slice_in(self, self.alloc.clone())
+    }
+}
+
+impl<T: Clone> From<&[T]> for ByteGuard<[T]> {
+    fn from(values: &[T]) -> Self {
+        Self::from_slice(values)
+    }
+}
+
+impl<T: ?Sized + PartialEq, A: RawAllocator> PartialEq for ByteGuard<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq, A: RawAllocator> Eq for ByteGuard<T, A> {}
+
+impl<T: ?Sized + PartialOrd, A: RawAllocator> PartialOrd for ByteGuard<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
     }
 }
 
-impl<T> Drop for ByteGuard<T> {
+impl<T: ?Sized + Ord, A: RawAllocator> Ord for ByteGuard<T, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + hash::Hash, A: RawAllocator> hash::Hash for ByteGuard<T, A> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: ?Sized, A: RawAllocator> Drop for ByteGuard<T, A> {
     fn drop(&mut self) {
-        unsafe { crate::dealloc(self.ptr, self.layout) }
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            self.alloc.deallocate(self.ptr.as_ptr() as *mut u8, self.layout);
+        }
+    }
+}
+
+impl<'a, T: marker::Freeze> ShallowCopy<'a, T> {
+    /// Copies the bytes of `original` without taking ownership of anything it owns.
+    #[inline]
+    #[must_use]
+    pub fn new(original: &'a T) -> Self {
+        Self {
+            value: mem::ManuallyDrop::new(unsafe { ptr::read(original) }),
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ShallowCopy<'a, T> {
+    /// Returns the bytes of the copy.
+    #[inline]
+    #[must_use]
+    pub fn bytes(&self) -> ByteObject<'_, T> {
+        ByteObject::from(&*self.value)
+    }
+
+    /// Turns the copy into an owned value that will be dropped normally.
+    /// ## Safety
+    /// The copy shares everything the original owns, so the original must
+    /// be forgotten, or `T` must have no drop glue, or the returned value
+    /// must not be dropped.
+    #[inline]
+    #[must_use]
+    pub unsafe fn into_owned_unchecked(self) -> T {
+        mem::ManuallyDrop::into_inner(self.value)
+    }
+}
+
+impl<T> ops::Deref for ShallowCopy<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> AsRef<T> for ShallowCopy<'_, T> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ShallowCopy<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ShallowCopy").field(&*self.value).finish()
     }
 }
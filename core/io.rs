@@ -0,0 +1,366 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Cursors for reading and writing binary data.
+
+use core::{fmt, mem};
+
+use crate::{AsBytes, Endian, FromBytes, NoUninit};
+
+/// The error returned when a read or write runs past the end of the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnderrunError {
+    /// The number of bytes that were needed.
+    pub needed: usize,
+    /// The number of bytes that were left.
+    pub remaining: usize,
+}
+
+impl fmt::Display for UnderrunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "needed {} bytes, but only {} remain", self.needed, self.remaining)
+    }
+}
+
+impl core::error::Error for UnderrunError {}
+
+/// Returns how many bytes are needed to move `pos` to a multiple of `align`.
+///
+/// ## Panics
+/// Panics if `align` is not a power of two.
+#[inline]
+fn padding_for(pos: usize, align: usize) -> usize {
+    assert!(align.is_power_of_two(), "align must be a power of two");
+    pos.wrapping_neg() & (align - 1)
+}
+
+/// A cursor that reads values out of a byte slice.
+///
+/// Every read advances the cursor, or fails with an [`UnderrunError`]
+/// and leaves it where it was.
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+macro_rules! read_methods {
+    ($($t:ty: $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Reads a little endian `", stringify!($t), "`.")]
+            #[inline]
+            pub fn $le(&mut self) -> Result<$t, UnderrunError> {
+                self.read_array().map(<$t>::from_le_bytes)
+            }
+
+            #[doc = concat!("Reads a big endian `", stringify!($t), "`.")]
+            #[inline]
+            pub fn $be(&mut self) -> Result<$t, UnderrunError> {
+                self.read_array().map(<$t>::from_be_bytes)
+            }
+        )*
+    };
+}
+
+impl<'a> ByteReader<'a> {
+    /// Creates a reader at the start of `bytes`.
+    #[inline]
+    #[must_use]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the offset of the cursor from the start of the buffer.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes left to read.
+    #[inline]
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Returns if there are no bytes left to read.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the bytes left to read, without advancing.
+    #[inline]
+    #[must_use]
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    /// Reads the next `len` bytes.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], UnderrunError> {
+        let remaining = self.remaining();
+        if len > remaining {
+            return Err(UnderrunError { needed: len, remaining });
+        }
+        let res = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(res)
+    }
+
+    /// Reads the next `N` bytes into an array.
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], UnderrunError> {
+        let mut res = [0; N];
+        res.copy_from_slice(self.read_bytes(N)?);
+        Ok(res)
+    }
+
+    /// Skips the next `len` bytes.
+    #[inline]
+    pub fn skip(&mut self, len: usize) -> Result<(), UnderrunError> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    /// Skips to the next offset that is a multiple of `align`.
+    ///
+    /// ## Panics
+    /// Panics if `align` is not a power of two.
+    #[inline]
+    pub fn align_to(&mut self, align: usize) -> Result<(), UnderrunError> {
+        self.skip(padding_for(self.pos, align))
+    }
+
+    /// Reads a `u8`.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, UnderrunError> {
+        self.read_array().map(|[b]| b)
+    }
+
+    /// Reads an `i8`.
+    #[inline]
+    pub fn read_i8(&mut self) -> Result<i8, UnderrunError> {
+        self.read_array().map(i8::from_ne_bytes)
+    }
+
+    read_methods! {
+        u16: read_u16_le, read_u16_be;
+        u32: read_u32_le, read_u32_be;
+        u64: read_u64_le, read_u64_be;
+        u128: read_u128_le, read_u128_be;
+        i16: read_i16_le, read_i16_be;
+        i32: read_i32_le, read_i32_be;
+        i64: read_i64_le, read_i64_be;
+        i128: read_i128_le, read_i128_be;
+        f32: read_f32_le, read_f32_be;
+        f64: read_f64_le, read_f64_be;
+    }
+
+    /// Reads a value in native byte order. The data does not need to be aligned.
+    #[inline]
+    pub fn read<T: FromBytes>(&mut self) -> Result<T, UnderrunError> {
+        let bytes = self.read_bytes(mem::size_of::<T>())?;
+        Ok(unsafe { (bytes.as_ptr() as *const T).read_unaligned() })
+    }
+
+    /// Reads a little endian value.
+    #[inline]
+    pub fn read_le<T: Endian>(&mut self) -> Result<T, UnderrunError> {
+        self.read().map(T::from_le)
+    }
+
+    /// Reads a big endian value.
+    #[inline]
+    pub fn read_be<T: Endian>(&mut self) -> Result<T, UnderrunError> {
+        self.read().map(T::from_be)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ByteReader<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<'a, T> From<crate::ByteObject<'a, T>> for ByteReader<'a> {
+    fn from(object: crate::ByteObject<'a, T>) -> Self {
+        Self::new(object.bytes())
+    }
+}
+
+/// A cursor that writes values into a byte slice.
+///
+/// Every write advances the cursor, or fails with an [`UnderrunError`]
+/// and leaves both the cursor and the buffer untouched.
+#[derive(Debug)]
+pub struct ByteWriter<'a> {
+    bytes: &'a mut [u8],
+    pos: usize,
+}
+
+macro_rules! write_methods {
+    ($($t:ty: $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Writes a little endian `", stringify!($t), "`.")]
+            #[inline]
+            pub fn $le(&mut self, value: $t) -> Result<(), UnderrunError> {
+                self.write_bytes(&value.to_le_bytes())
+            }
+
+            #[doc = concat!("Writes a big endian `", stringify!($t), "`.")]
+            #[inline]
+            pub fn $be(&mut self, value: $t) -> Result<(), UnderrunError> {
+                self.write_bytes(&value.to_be_bytes())
+            }
+        )*
+    };
+}
+
+impl<'a> ByteWriter<'a> {
+    /// Creates a writer at the start of `bytes`.
+    #[inline]
+    #[must_use]
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the offset of the cursor from the start of the buffer.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes left to write.
+    #[inline]
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Returns if there is no room left to write.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the bytes written so far.
+    #[inline]
+    #[must_use]
+    pub fn written(&self) -> &[u8] {
+        &self.bytes[..self.pos]
+    }
+
+    /// Returns the next `len` bytes and advances past them.
+    fn advance(&mut self, len: usize) -> Result<&mut [u8], UnderrunError> {
+        let remaining = self.remaining();
+        if len > remaining {
+            return Err(UnderrunError { needed: len, remaining });
+        }
+        let start = self.pos;
+        self.pos += len;
+        Ok(&mut self.bytes[start..start + len])
+    }
+
+    /// Writes `bytes`.
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), UnderrunError> {
+        self.advance(bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Writes an array of bytes.
+    #[inline]
+    pub fn write_array<const N: usize>(&mut self, bytes: [u8; N]) -> Result<(), UnderrunError> {
+        self.write_bytes(&bytes)
+    }
+
+    /// Skips the next `len` bytes, leaving them as they are.
+    #[inline]
+    pub fn skip(&mut self, len: usize) -> Result<(), UnderrunError> {
+        self.advance(len).map(|_| ())
+    }
+
+    /// Skips to the next offset that is a multiple of `align`,
+    /// leaving the skipped bytes as they are.
+    ///
+    /// ## Panics
+    /// Panics if `align` is not a power of two.
+    #[inline]
+    pub fn align_to(&mut self, align: usize) -> Result<(), UnderrunError> {
+        self.skip(padding_for(self.pos, align))
+    }
+
+    /// Writes a `u8`.
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), UnderrunError> {
+        self.write_array([value])
+    }
+
+    /// Writes an `i8`.
+    #[inline]
+    pub fn write_i8(&mut self, value: i8) -> Result<(), UnderrunError> {
+        self.write_array(value.to_ne_bytes())
+    }
+
+    write_methods! {
+        u16: write_u16_le, write_u16_be;
+        u32: write_u32_le, write_u32_be;
+        u64: write_u64_le, write_u64_be;
+        u128: write_u128_le, write_u128_be;
+        i16: write_i16_le, write_i16_be;
+        i32: write_i32_le, write_i32_be;
+        i64: write_i64_le, write_i64_be;
+        i128: write_i128_le, write_i128_be;
+        f32: write_f32_le, write_f32_be;
+        f64: write_f64_le, write_f64_be;
+    }
+
+    /// Writes a value in native byte order.
+    #[inline]
+    pub fn write<T: NoUninit>(&mut self, value: &T) -> Result<(), UnderrunError> {
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// Writes a little endian value.
+    #[inline]
+    pub fn write_le<T: Endian>(&mut self, value: T) -> Result<(), UnderrunError> {
+        self.write(&value.to_le())
+    }
+
+    /// Writes a big endian value.
+    #[inline]
+    pub fn write_be<T: Endian>(&mut self, value: T) -> Result<(), UnderrunError> {
+        self.write(&value.to_be())
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for ByteWriter<'a> {
+    fn from(bytes: &'a mut [u8]) -> Self {
+        Self::new(bytes)
+    }
+}
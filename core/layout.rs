@@ -0,0 +1,140 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Struct layout introspection.
+
+/// Where a field lives inside its struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldLayout {
+    /// The name of the field, or its index for tuple structs.
+    pub name: &'static str,
+    /// The type of the field, as written in the struct.
+    pub ty: &'static str,
+    /// The offset of the field from the start of the struct, in bytes.
+    pub offset: usize,
+    /// The size of the field, in bytes.
+    pub size: usize,
+    /// The alignment of the field's type, in bytes.
+    pub align: usize,
+}
+
+impl FieldLayout {
+    /// Returns the range of bytes the field occupies.
+    #[inline]
+    #[must_use]
+    pub const fn range(&self) -> core::ops::Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+/// A run of padding bytes inside a struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaddingHole {
+    /// The offset of the first padding byte.
+    pub offset: usize,
+    /// The number of padding bytes.
+    pub size: usize,
+}
+
+impl PaddingHole {
+    /// Returns the range of bytes the hole covers.
+    #[inline]
+    #[must_use]
+    pub const fn range(&self) -> core::ops::Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+/// A struct whose field layout is known at compile time.
+///
+/// Implement it with `#[derive(MemLayout)]`.
+pub trait MemLayout: Sized {
+    /// The fields of the struct, in declaration order.
+    const FIELDS: &'static [FieldLayout];
+
+    /// The padding holes of the struct, in address order,
+    /// including any padding at the end.
+    const PADDING: &'static [PaddingHole];
+
+    /// Returns the field that covers the byte at `offset`,
+    /// or `None` if that byte is padding or out of bounds.
+    #[must_use]
+    fn field_at(offset: usize) -> Option<&'static FieldLayout> {
+        Self::FIELDS.iter().find(|f| f.range().contains(&offset))
+    }
+
+    /// Returns if the struct has any padding bytes.
+    #[inline]
+    #[must_use]
+    fn has_padding() -> bool {
+        !Self::PADDING.is_empty()
+    }
+}
+
+/// Computes the padding holes of a struct of `size` bytes with `fields`.
+///
+/// Returns the holes and how many of them are used; `N` must be at least
+/// one more than the number of fields. Used by `#[derive(MemLayout)]`.
+#[doc(hidden)]
+#[must_use]
+pub const fn padding_holes<const N: usize>(fields: &[FieldLayout], size: usize) -> ([PaddingHole; N], usize) {
+    assert!(fields.len() < N);
+
+    // Sort the (offset, size) pairs by offset.
+    let mut spans = [(0usize, 0usize); N];
+    let mut i = 0;
+    while i < fields.len() {
+        let mut j = i;
+        while j > 0 && spans[j - 1].0 > fields[i].offset {
+            spans[j] = spans[j - 1];
+            j -= 1;
+        }
+        spans[j] = (fields[i].offset, fields[i].size);
+        i += 1;
+    }
+
+    let mut holes = [PaddingHole { offset: 0, size: 0 }; N];
+    let mut count = 0;
+    let mut cursor = 0;
+    i = 0;
+    while i < fields.len() {
+        let (offset, len) = spans[i];
+        if len != 0 {
+            if offset > cursor {
+                holes[count] = PaddingHole { offset: cursor, size: offset - cursor };
+                count += 1;
+            }
+            if offset + len > cursor {
+                cursor = offset + len;
+            }
+        }
+        i += 1;
+    }
+    if size > cursor {
+        holes[count] = PaddingHole { offset: cursor, size: size - cursor };
+        count += 1;
+    }
+    (holes, count)
+}
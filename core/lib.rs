@@ -26,8 +26,8 @@
 
 #![no_std]
 
-#![feature(const_mut_refs)]
 #![feature(ptr_metadata)]
+#![feature(freeze)]
 
 #![forbid(
     missing_debug_implementations,
@@ -44,6 +44,8 @@
     unused,
 )]
 
+#[cfg(feature = "std")]
+extern crate std;
 #[cfg(feature = "reveal_hidden")]
 #[allow(pub_use_of_private_extern_crate, forbidden_lint_groups, future_incompatible, unused)]
 pub extern crate alloc as liballoc;
@@ -61,13 +63,104 @@ pub mod bytes;
 #[doc(hidden)]
 pub(crate) mod bytes;
 #[cfg(feature = "reveal_hidden")]
+pub mod allocator;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod allocator;
+#[cfg(feature = "reveal_hidden")]
+pub mod arena;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod arena;
+#[cfg(feature = "reveal_hidden")]
+pub mod buddy;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod buddy;
+#[cfg(feature = "reveal_hidden")]
+pub mod heap;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod heap;
+#[cfg(feature = "reveal_hidden")]
+pub mod slab;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod slab;
+#[cfg(feature = "reveal_hidden")]
+pub mod slotmap;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod slotmap;
+#[cfg(feature = "reveal_hidden")]
+pub mod pod;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod pod;
+#[cfg(feature = "reveal_hidden")]
+pub mod cast;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod cast;
+#[cfg(feature = "reveal_hidden")]
+pub mod endian;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod endian;
+#[cfg(feature = "reveal_hidden")]
+pub mod io;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod io;
+#[cfg(feature = "reveal_hidden")]
+pub mod layout;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod layout;
+#[cfg(feature = "reveal_hidden")]
+pub mod reveal;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod reveal;
+#[cfg(feature = "reveal_hidden")]
+pub mod hexdump;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod hexdump;
+#[cfg(feature = "reveal_hidden")]
+pub mod diff;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod diff;
+#[cfg(feature = "reveal_hidden")]
 pub mod dynarray;
 #[cfg(not(feature = "reveal_hidden"))]
 #[doc(hidden)]
 pub(crate) mod dynarray;
 #[cfg(feature = "reveal_hidden")]
+pub mod list;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod list;
+#[cfg(feature = "reveal_hidden")]
+pub mod rel;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod rel;
+#[cfg(feature = "reveal_hidden")]
+pub mod spin;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod spin;
+#[cfg(feature = "reveal_hidden")]
+pub mod tagged;
+#[cfg(not(feature = "reveal_hidden"))]
+#[doc(hidden)]
+pub(crate) mod tagged;
 pub mod prelude;
 mod impls;
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod procmaps;
 pub use liballoc::alloc::{
     handle_alloc_error,
     alloc as malloc,
@@ -77,3 +170,22 @@ pub use liballoc::alloc::{
 };
 pub use bytes::*;
 pub use nulls::*;
+pub use allocator::*;
+pub use arena::*;
+pub use buddy::*;
+pub use heap::*;
+pub use slab::*;
+pub use slotmap::*;
+pub use pod::*;
+pub use cast::*;
+pub use endian::*;
+pub use io::*;
+pub use layout::*;
+pub use reveal::*;
+pub use hexdump::*;
+pub use diff::*;
+pub use dynarray::{DynArray, RelDynArray};
+pub use list::{Adapter, IntrusiveList, ListLink};
+pub use rel::*;
+pub use spin::*;
+pub use tagged::*;
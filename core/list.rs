@@ -0,0 +1,438 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! An intrusive doubly-linked list.
+//!
+//! The list never allocates: every node embeds a [`ListLink`], and an
+//! [`Adapter`] tells the list where that link lives inside the node.
+//! Nodes are borrowed and pinned for as long as the list exists, so they can
+//! live anywhere that outlives it: in a `static`, in a pinned
+//! [`ByteGuard`](crate::ByteGuard), or on the stack with `core::pin::pin!`.
+
+use core::cell::Cell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::marker::{PhantomData, PhantomPinned};
+use core::pin::Pin;
+use core::ptr::NonNull;
+use core::{fmt, iter};
+
+/// The link a node embeds to be part of an [`IntrusiveList`].
+pub struct ListLink {
+    prev: Cell<Option<NonNull<ListLink>>>,
+    next: Cell<Option<NonNull<ListLink>>>,
+    linked: AtomicBool,
+    _pin: PhantomPinned,
+}
+
+impl ListLink {
+    /// Creates an unlinked link.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prev: Cell::new(None),
+            next: Cell::new(None),
+            linked: AtomicBool::new(false),
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// Returns if the link is part of a list.
+    #[inline]
+    #[must_use]
+    pub fn is_linked(&self) -> bool {
+        self.linked.load(Ordering::Acquire)
+    }
+
+    /// Resets the link to its unlinked state.
+    #[inline]
+    fn reset(&self) {
+        self.prev.set(None);
+        self.next.set(None);
+        self.linked.store(false, Ordering::Release);
+    }
+}
+
+// The neighbour pointers are only touched by the list that claimed the link,
+// which needs `&mut` to change them; claiming itself is atomic.
+unsafe impl Send for ListLink {}
+unsafe impl Sync for ListLink {}
+
+impl Default for ListLink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ListLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListLink").field("linked", &self.is_linked()).finish()
+    }
+}
+
+/// Tells an [`IntrusiveList`] where the [`ListLink`] lives inside a `T`.
+///
+/// Implement it with the `intrusive_adapter!` macro.
+/// ## Safety
+/// `OFFSET` must be the offset of a `ListLink` field of `T`.
+pub unsafe trait Adapter<T> {
+    /// The offset of the link from the start of `T`, in bytes.
+    const OFFSET: usize;
+
+    /// Returns the link of `node`.
+    #[inline]
+    fn link(node: &T) -> &ListLink {
+        unsafe { &*(node as *const T).byte_add(Self::OFFSET).cast::<ListLink>() }
+    }
+
+    /// Returns the node that contains `link`.
+    /// ## Safety
+    /// `link` must be the link of a `T` that is valid for `'a`.
+    #[inline]
+    unsafe fn node<'a>(link: NonNull<ListLink>) -> &'a T {
+        &*link.as_ptr().byte_sub(Self::OFFSET).cast::<T>()
+    }
+}
+
+/// An intrusive doubly-linked list of pinned nodes that outlive `'a`.
+///
+/// Pushing, popping and unlinking are O(1) and never allocate. Dropping the
+/// list unlinks every node, so they can be pushed to another list afterwards.
+pub struct IntrusiveList<'a, T, A: Adapter<T>> {
+    head: Option<NonNull<ListLink>>,
+    tail: Option<NonNull<ListLink>>,
+    len: usize,
+    _marker: PhantomData<(Pin<&'a T>, A)>,
+}
+
+impl<'a, T, A: Adapter<T>> IntrusiveList<'a, T, A> {
+    /// Creates an empty list.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { head: None, tail: None, len: 0, _marker: PhantomData }
+    }
+
+    /// Returns the number of nodes in the list.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns if the list has no nodes.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the first node.
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<Pin<&'a T>> {
+        self.head.map(|link| unsafe { Self::pinned(link) })
+    }
+
+    /// Returns the last node.
+    #[inline]
+    #[must_use]
+    pub fn back(&self) -> Option<Pin<&'a T>> {
+        self.tail.map(|link| unsafe { Self::pinned(link) })
+    }
+
+    /// Adds `node` to the front of the list.
+    /// ## Panics
+    /// Panics if `node` is already part of a list.
+    pub fn push_front(&mut self, node: Pin<&'a T>) {
+        let link = Self::claim(node);
+        unsafe { self.link_between(link, None, self.head) };
+    }
+
+    /// Adds `node` to the back of the list.
+    /// ## Panics
+    /// Panics if `node` is already part of a list.
+    pub fn push_back(&mut self, node: Pin<&'a T>) {
+        let link = Self::claim(node);
+        unsafe { self.link_between(link, self.tail, None) };
+    }
+
+    /// Removes the first node and returns it.
+    pub fn pop_front(&mut self) -> Option<Pin<&'a T>> {
+        let link = self.head?;
+        Some(unsafe { self.unlink_link(link) })
+    }
+
+    /// Removes the last node and returns it.
+    pub fn pop_back(&mut self) -> Option<Pin<&'a T>> {
+        let link = self.tail?;
+        Some(unsafe { self.unlink_link(link) })
+    }
+
+    /// Removes `node` from the list in O(1).
+    /// ## Safety
+    /// `node` must be part of this list, not just of any list.
+    pub unsafe fn unlink(&mut self, node: Pin<&'a T>) {
+        let link = NonNull::from(A::link(&node));
+        self.unlink_link(link);
+    }
+
+    /// Unlinks every node.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns an iterator over the nodes, from front to back.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, 'a, T, A> {
+        Iter { head: self.head, tail: self.tail, len: self.len, _marker: PhantomData }
+    }
+
+    /// Returns a cursor at the first node, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, 'a, T, A> {
+        CursorMut { current: self.head, list: self }
+    }
+
+    /// Returns a cursor at the last node, or at the ghost position if the list is empty.
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, 'a, T, A> {
+        CursorMut { current: self.tail, list: self }
+    }
+
+    /// Marks the link of `node` as linked, panicking if it already is.
+    fn claim(node: Pin<&'a T>) -> NonNull<ListLink> {
+        let link = A::link(node.get_ref());
+        let claimed = link.linked.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire);
+        assert!(claimed.is_ok(), "node is already linked into a list");
+        NonNull::from(link)
+    }
+
+    /// Returns the pinned node of `link`.
+    /// ## Safety
+    /// `link` must be linked into this list.
+    #[inline]
+    unsafe fn pinned(link: NonNull<ListLink>) -> Pin<&'a T> {
+        Pin::new_unchecked(A::node(link))
+    }
+
+    /// Links `link` between `prev` and `next`, which must be neighbours in this list.
+    unsafe fn link_between(&mut self, link: NonNull<ListLink>, prev: Option<NonNull<ListLink>>, next: Option<NonNull<ListLink>>) {
+        let l = link.as_ref();
+        l.prev.set(prev);
+        l.next.set(next);
+        match prev {
+            Some(prev) => prev.as_ref().next.set(Some(link)),
+            None => self.head = Some(link),
+        }
+        match next {
+            Some(next) => next.as_ref().prev.set(Some(link)),
+            None => self.tail = Some(link),
+        }
+        self.len += 1;
+    }
+
+    /// Unlinks `link`, which must be linked into this list, and returns its node.
+    unsafe fn unlink_link(&mut self, link: NonNull<ListLink>) -> Pin<&'a T> {
+        let l = link.as_ref();
+        let (prev, next) = (l.prev.get(), l.next.get());
+        match prev {
+            Some(prev) => prev.as_ref().next.set(next),
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => next.as_ref().prev.set(prev),
+            None => self.tail = prev,
+        }
+        l.reset();
+        self.len -= 1;
+        Self::pinned(link)
+    }
+}
+
+unsafe impl<T: Sync, A: Adapter<T>> Send for IntrusiveList<'_, T, A> {}
+unsafe impl<T: Sync, A: Adapter<T>> Sync for IntrusiveList<'_, T, A> {}
+
+impl<T, A: Adapter<T>> Default for IntrusiveList<'_, T, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Adapter<T>> Drop for IntrusiveList<'_, T, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug, A: Adapter<T>> fmt::Debug for IntrusiveList<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'l, 'a, T, A: Adapter<T>> IntoIterator for &'l IntrusiveList<'a, T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'l, 'a, T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the nodes of an [`IntrusiveList`].
+pub struct Iter<'l, 'a, T, A: Adapter<T>> {
+    head: Option<NonNull<ListLink>>,
+    tail: Option<NonNull<ListLink>>,
+    len: usize,
+    _marker: PhantomData<(&'l IntrusiveList<'a, T, A>, &'a T)>,
+}
+
+impl<'a, T, A: Adapter<T>> Iterator for Iter<'_, 'a, T, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let link = self.head?;
+        self.len -= 1;
+        unsafe {
+            self.head = link.as_ref().next.get();
+            Some(A::node(link))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, A: Adapter<T>> DoubleEndedIterator for Iter<'_, 'a, T, A> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let link = self.tail?;
+        self.len -= 1;
+        unsafe {
+            self.tail = link.as_ref().prev.get();
+            Some(A::node(link))
+        }
+    }
+}
+
+impl<T, A: Adapter<T>> ExactSizeIterator for Iter<'_, '_, T, A> {}
+impl<T, A: Adapter<T>> iter::FusedIterator for Iter<'_, '_, T, A> {}
+
+impl<T, A: Adapter<T>> Clone for Iter<'_, '_, T, A> {
+    fn clone(&self) -> Self {
+        Self { head: self.head, tail: self.tail, len: self.len, _marker: PhantomData }
+    }
+}
+
+impl<T, A: Adapter<T>> fmt::Debug for Iter<'_, '_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter").field("len", &self.len).finish()
+    }
+}
+
+/// A cursor over an [`IntrusiveList`] that can unlink and insert nodes.
+///
+/// The cursor points at a node, or at the ghost position between the back
+/// and the front of the list.
+pub struct CursorMut<'l, 'a, T, A: Adapter<T>> {
+    current: Option<NonNull<ListLink>>,
+    list: &'l mut IntrusiveList<'a, T, A>,
+}
+
+impl<'a, T, A: Adapter<T>> CursorMut<'_, 'a, T, A> {
+    /// Returns the node at the cursor, or `None` at the ghost position.
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<Pin<&'a T>> {
+        self.current.map(|link| unsafe { IntrusiveList::<T, A>::pinned(link) })
+    }
+
+    /// Moves to the next node, or from the back to the ghost position
+    /// and from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            Some(link) => unsafe { link.as_ref().next.get() },
+            None => self.list.head,
+        };
+    }
+
+    /// Moves to the previous node, or from the front to the ghost position
+    /// and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            Some(link) => unsafe { link.as_ref().prev.get() },
+            None => self.list.tail,
+        };
+    }
+
+    /// Unlinks the node at the cursor in O(1), moves to the next node, and
+    /// returns the unlinked one. Does nothing at the ghost position.
+    pub fn remove_current(&mut self) -> Option<Pin<&'a T>> {
+        let link = self.current?;
+        unsafe {
+            self.current = link.as_ref().next.get();
+            Some(self.list.unlink_link(link))
+        }
+    }
+
+    /// Inserts `node` before the cursor, or at the back at the ghost position.
+    /// ## Panics
+    /// Panics if `node` is already part of a list.
+    pub fn insert_before(&mut self, node: Pin<&'a T>) {
+        let link = IntrusiveList::<T, A>::claim(node);
+        unsafe {
+            match self.current {
+                Some(current) => self.list.link_between(link, current.as_ref().prev.get(), Some(current)),
+                None => self.list.link_between(link, self.list.tail, None),
+            }
+        }
+    }
+
+    /// Inserts `node` after the cursor, or at the front at the ghost position.
+    /// ## Panics
+    /// Panics if `node` is already part of a list.
+    pub fn insert_after(&mut self, node: Pin<&'a T>) {
+        let link = IntrusiveList::<T, A>::claim(node);
+        unsafe {
+            match self.current {
+                Some(current) => self.list.link_between(link, Some(current), current.as_ref().next.get()),
+                None => self.list.link_between(link, None, self.list.head),
+            }
+        }
+    }
+}
+
+impl<T, A: Adapter<T>> fmt::Debug for CursorMut<'_, '_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorMut").field("current", &self.current).finish()
+    }
+}
@@ -29,6 +29,8 @@ use core::fmt::{
     Debug,
     self
 };
+use core::hash::{Hash, Hasher};
+use core::ptr::Pointee;
 
 /// A safe wrapper for Undefined Behavior.
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -45,8 +47,10 @@ impl Display for Undefined {
 }
 
 /// A safe wrapper for `ptr::null()`.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
-pub struct Null<T: ?Sized + core::ptr::Thin> {
+///
+/// For unsized types the null pointer still carries metadata,
+/// such as a slice length or a vtable.
+pub struct Null<T: ?Sized> {
     _private: *const T
 }
 impl<T: ?Sized + core::ptr::Thin> Null<T> {
@@ -60,19 +64,375 @@ impl<T: ?Sized + core::ptr::Thin> Null<T> {
         }
     }
 }
+impl<T: ?Sized> Null<T> {
+    /// Creates a new `Null` instance with the given pointer metadata.
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_metadata(metadata: <T as Pointee>::Metadata) -> Self {
+        Self {
+            _private: core::ptr::from_raw_parts(core::ptr::null::<()>(), metadata)
+        }
+    }
+
+    /// Returns the pointer metadata.
+    #[inline(always)]
+    #[must_use]
+    pub fn metadata(&self) -> <T as Pointee>::Metadata {
+        core::ptr::metadata(self._private)
+    }
+
+    /// Returns the null pointer, with its metadata.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const T {
+        self._private
+    }
+}
+impl<T: ?Sized> Clone for Null<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T: ?Sized> Copy for Null<T> {}
+impl<T: ?Sized> PartialEq for Null<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.metadata() == other.metadata()
+    }
+}
+impl<T: ?Sized> Eq for Null<T> {}
+impl<T: ?Sized> Hash for Null<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.metadata().hash(state)
+    }
+}
 impl<T> From<T> for Null<T> {
     /// Ignores the value and returns a `Null` instance.
     fn from(_: T) -> Self {
         Self::new()
     }
 }
-impl<T: ?Sized + core::ptr::Thin> Debug for Null<T> {
+impl<T: ?Sized> Debug for Null<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f,"null")
     }
 }
-impl<T: ?Sized + core::ptr::Thin> Display for Null<T> {
+impl<T: ?Sized> Display for Null<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f,"Null")
     }
 }
+
+/// A non-owning pointer that may be null.
+///
+/// Has the same layout as `*mut T`, so it can be passed across FFI boundaries.
+/// Dereferencing it is unsafe, but checking for null is not.
+#[repr(transparent)]
+pub struct Nullable<T: ?Sized> {
+    ptr: *mut T
+}
+impl<T: ?Sized + core::ptr::Thin> Nullable<T> {
+    /// Creates a null `Nullable`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn null() -> Self {
+        Self { ptr: core::ptr::null_mut() }
+    }
+}
+impl<T: ?Sized> Nullable<T> {
+    /// Wraps a raw pointer, which may be null.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(ptr: *mut T) -> Self {
+        Self { ptr }
+    }
+
+    /// Points to the value behind a shared reference.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_ref(value: &T) -> Self {
+        Self { ptr: value as *const T as *mut T }
+    }
+
+    /// Points to the value behind a mutable reference.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_mut(value: &mut T) -> Self {
+        Self { ptr: value }
+    }
+
+    /// Returns if the pointer is null.
+    #[inline(always)]
+    #[must_use]
+    pub fn is_null(self) -> bool {
+        self.ptr.is_null()
+    }
+
+    /// Returns the raw pointer.
+    #[inline(always)]
+    #[must_use]
+    pub const fn as_ptr(self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns the pointer as a `NonNull`, or `None` if it is null.
+    #[inline(always)]
+    #[must_use]
+    pub fn to_non_null(self) -> Option<core::ptr::NonNull<T>> {
+        core::ptr::NonNull::new(self.ptr)
+    }
+
+    /// Returns a shared reference to the value, or `None` if the pointer is null.
+    /// ## Safety
+    /// If the pointer is not null, it must be valid for reads of `T`
+    /// for `'a`, and the value must not be mutated during `'a`.
+    #[inline(always)]
+    #[must_use]
+    pub unsafe fn as_ref<'a>(self) -> Option<&'a T> {
+        self.ptr.as_ref()
+    }
+
+    /// Returns a mutable reference to the value, or `None` if the pointer is null.
+    /// ## Safety
+    /// If the pointer is not null, it must be valid for reads and writes
+    /// of `T` for `'a`, and the value must not be accessed through any
+    /// other pointer during `'a`.
+    #[inline(always)]
+    #[must_use]
+    pub unsafe fn as_mut<'a>(self) -> Option<&'a mut T> {
+        self.ptr.as_mut()
+    }
+
+    /// Maps the pointer with `f` if it is not null, and keeps it null otherwise.
+    #[inline]
+    #[must_use]
+    pub fn map<U: ?Sized + core::ptr::Thin, F: FnOnce(core::ptr::NonNull<T>) -> core::ptr::NonNull<U>>(self, f: F) -> Nullable<U> {
+        match self.to_non_null() {
+            Some(ptr) => Nullable::new(f(ptr).as_ptr()),
+            None => Nullable::null(),
+        }
+    }
+
+    /// Casts to a pointer to another type, dropping any metadata.
+    #[inline(always)]
+    #[must_use]
+    pub const fn cast<U>(self) -> Nullable<U> {
+        Nullable { ptr: self.ptr.cast() }
+    }
+}
+impl<T: ?Sized> Clone for Nullable<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T: ?Sized> Copy for Nullable<T> {}
+impl<T: ?Sized> PartialEq for Nullable<T> {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.ptr, other.ptr)
+    }
+}
+impl<T: ?Sized> Eq for Nullable<T> {}
+impl<T: ?Sized> Hash for Nullable<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ptr.hash(state)
+    }
+}
+impl<T: ?Sized + core::ptr::Thin> Default for Nullable<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+impl<T: ?Sized + core::ptr::Thin> From<Option<core::ptr::NonNull<T>>> for Nullable<T> {
+    fn from(ptr: Option<core::ptr::NonNull<T>>) -> Self {
+        match ptr {
+            Some(ptr) => Self::new(ptr.as_ptr()),
+            None => Self::null(),
+        }
+    }
+}
+impl<T: ?Sized> From<Nullable<T>> for Option<core::ptr::NonNull<T>> {
+    fn from(ptr: Nullable<T>) -> Self {
+        ptr.to_non_null()
+    }
+}
+impl<T: ?Sized> From<core::ptr::NonNull<T>> for Nullable<T> {
+    fn from(ptr: core::ptr::NonNull<T>) -> Self {
+        Self::new(ptr.as_ptr())
+    }
+}
+impl<T: ?Sized> From<&T> for Nullable<T> {
+    fn from(value: &T) -> Self {
+        Self::from_ref(value)
+    }
+}
+impl<T: ?Sized> From<&mut T> for Nullable<T> {
+    fn from(value: &mut T) -> Self {
+        Self::from_mut(value)
+    }
+}
+impl<T: ?Sized> From<Null<T>> for Nullable<T> {
+    fn from(null: Null<T>) -> Self {
+        Self::new(null.as_ptr() as *mut T)
+    }
+}
+impl<T: ?Sized> Debug for Nullable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_null() {
+            write!(f,"null")
+        } else {
+            fmt::Pointer::fmt(&self.ptr, f)
+        }
+    }
+}
+
+/// A value that may be present, null, or undefined.
+///
+/// Models the three states FFI code has to tell apart:
+/// a value, an explicit [`Null`], and a missing or [`Undefined`] one.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaybeUndefined<T> {
+    /// A value.
+    Value(T),
+    /// An explicit null.
+    Null,
+    /// No value at all.
+    #[default]
+    Undefined,
+}
+impl<T> MaybeUndefined<T> {
+    /// Returns if this is a value.
+    #[inline]
+    #[must_use]
+    pub const fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    /// Returns if this is null.
+    #[inline]
+    #[must_use]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns if this is undefined.
+    #[inline]
+    #[must_use]
+    pub const fn is_undefined(&self) -> bool {
+        matches!(self, Self::Undefined)
+    }
+
+    /// Returns the value, or `None` if it is null or undefined.
+    #[inline]
+    #[must_use]
+    pub fn value(self) -> Option<T> {
+        match self {
+            Self::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Converts from `&MaybeUndefined<T>` to `MaybeUndefined<&T>`.
+    #[inline]
+    #[must_use]
+    pub const fn as_ref(&self) -> MaybeUndefined<&T> {
+        match self {
+            Self::Value(v) => MaybeUndefined::Value(v),
+            Self::Null => MaybeUndefined::Null,
+            Self::Undefined => MaybeUndefined::Undefined,
+        }
+    }
+
+    /// Converts from `&mut MaybeUndefined<T>` to `MaybeUndefined<&mut T>`.
+    #[inline]
+    #[must_use]
+    pub fn as_mut(&mut self) -> MaybeUndefined<&mut T> {
+        match self {
+            Self::Value(v) => MaybeUndefined::Value(v),
+            Self::Null => MaybeUndefined::Null,
+            Self::Undefined => MaybeUndefined::Undefined,
+        }
+    }
+
+    /// Maps the value with `f`, keeping null and undefined as they are.
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> MaybeUndefined<U> {
+        self.and_then(|v| MaybeUndefined::Value(f(v)))
+    }
+
+    /// Calls `f` with the value, keeping null and undefined as they are.
+    #[inline]
+    pub fn and_then<U, F: FnOnce(T) -> MaybeUndefined<U>>(self, f: F) -> MaybeUndefined<U> {
+        match self {
+            Self::Value(v) => f(v),
+            Self::Null => MaybeUndefined::Null,
+            Self::Undefined => MaybeUndefined::Undefined,
+        }
+    }
+
+    /// Returns `self` if it is defined, and `other` otherwise.
+    #[inline]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Undefined => other,
+            defined => defined,
+        }
+    }
+
+    /// Returns `self` if it is defined, and the result of `f` otherwise.
+    #[inline]
+    pub fn or_else<F: FnOnce() -> Self>(self, f: F) -> Self {
+        match self {
+            Self::Undefined => f(),
+            defined => defined,
+        }
+    }
+
+    /// Returns the value, or `default` if it is null or undefined.
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T {
+        self.value().unwrap_or(default)
+    }
+
+    /// Returns the value.
+    /// ## Panics
+    /// Panics if it is null or undefined.
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        match self {
+            Self::Value(v) => v,
+            Self::Null => panic!("called `MaybeUndefined::unwrap()` on a `Null` value"),
+            Self::Undefined => panic!("called `MaybeUndefined::unwrap()` on an `Undefined` value"),
+        }
+    }
+
+    /// Collapses undefined into null, returning `None` only if undefined.
+    #[inline]
+    #[must_use]
+    pub fn defined(self) -> Option<Option<T>> {
+        match self {
+            Self::Value(v) => Some(Some(v)),
+            Self::Null => Some(None),
+            Self::Undefined => None,
+        }
+    }
+}
+impl<T> From<Option<T>> for MaybeUndefined<T> {
+    /// Maps `None` to `Null`.
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => Self::Value(v),
+            None => Self::Null,
+        }
+    }
+}
+impl<T> From<Undefined> for MaybeUndefined<T> {
+    fn from(_: Undefined) -> Self {
+        Self::Undefined
+    }
+}
+impl<T> From<Nullable<T>> for MaybeUndefined<core::ptr::NonNull<T>> {
+    /// Maps a null pointer to `Null`.
+    fn from(ptr: Nullable<T>) -> Self {
+        ptr.to_non_null().into()
+    }
+}
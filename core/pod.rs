@@ -0,0 +1,116 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+//! Marker traits for plain old data.
+
+use core::{marker, ptr};
+
+/// A type for which the all-zero bit pattern is a valid value.
+/// ## Safety
+/// `core::mem::zeroed::<Self>()` must be a valid value of `Self`.
+pub unsafe trait Zeroable: Sized {
+    /// Returns the all-zero value of `Self`.
+    #[inline]
+    #[must_use]
+    fn zeroed() -> Self {
+        unsafe { core::mem::zeroed() }
+    }
+}
+
+/// A type for which every bit pattern is a valid value.
+///
+/// Unlike [`Pod`], a `FromBytes` type may have padding, so it can be read
+/// from bytes but not necessarily viewed as bytes.
+///
+/// Implement it with `#[derive(FromBytes)]`.
+/// ## Safety
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value of `Self`.
+pub unsafe trait FromBytes: Zeroable {}
+
+/// A type whose values contain no uninitialized bytes, such as padding.
+///
+/// Implement it with `#[derive(AsBytes)]`.
+/// ## Safety
+/// Every byte of every value of `Self` must be initialized.
+pub unsafe trait NoUninit: Sized {}
+
+/// Plain old data.
+///
+/// A `Pod` type has no padding, no drop glue, and every bit pattern of
+/// its size is a valid value, so it can be copied byte by byte.
+///
+/// Implement it with `#[derive(Pod)]`, which checks all of the above at
+/// compile time.
+/// ## Safety
+/// Every bit pattern must be a valid value of `Self`,
+/// `Self` must have no padding bytes, and it must not need dropping.
+pub unsafe trait Pod: FromBytes + NoUninit {}
+
+/// Clones plain old data by copying its bytes.
+///
+/// This is the safe counterpart of [`ByteClone::byte_clone`](crate::prelude::ByteClone::byte_clone).
+#[inline]
+#[must_use]
+pub fn byte_clone<T: Pod>(value: &T) -> T {
+    unsafe { ptr::read(value) }
+}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(
+            unsafe impl Zeroable for $t {}
+            unsafe impl FromBytes for $t {}
+            unsafe impl NoUninit for $t {}
+            unsafe impl Pod for $t {}
+        )*
+    };
+}
+
+impl_pod! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, () }
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] {}
+unsafe impl<T: NoUninit, const N: usize> NoUninit for [T; N] {}
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+unsafe impl<T: ?Sized> Zeroable for marker::PhantomData<T> {}
+unsafe impl<T: ?Sized> FromBytes for marker::PhantomData<T> {}
+unsafe impl<T: ?Sized> NoUninit for marker::PhantomData<T> {}
+unsafe impl<T: ?Sized> Pod for marker::PhantomData<T> {}
+
+unsafe impl<T: Zeroable> Zeroable for core::num::Wrapping<T> {}
+unsafe impl<T: FromBytes> FromBytes for core::num::Wrapping<T> {}
+unsafe impl<T: NoUninit> NoUninit for core::num::Wrapping<T> {}
+unsafe impl<T: Pod> Pod for core::num::Wrapping<T> {}
+
+unsafe impl Zeroable for bool {}
+unsafe impl NoUninit for bool {}
+unsafe impl Zeroable for char {}
+unsafe impl NoUninit for char {}
+
+unsafe impl<T: ?Sized + ptr::Thin> Zeroable for *const T {}
+unsafe impl<T: ?Sized + ptr::Thin> Zeroable for *mut T {}
+unsafe impl<T> Zeroable for Option<ptr::NonNull<T>> {}
+unsafe impl<T> Zeroable for Option<&T> {}
+unsafe impl<T> Zeroable for Option<&mut T> {}
@@ -8,25 +8,212 @@ pub trait PointerUtils<T> {
     fn cp(&self) -> *const T;
 }
 
+/// Arithmetic and alignment helpers for raw pointers.
+///
+/// All methods work on addresses only and never dereference the pointer,
+/// so they are safe to call; offsets are in bytes unless noted otherwise.
+pub trait PtrExt: Copy + Sized {
+    /// The same pointer, cast to bytes.
+    type Bytes;
+
+    /// Rounds the address up to a multiple of `align`, wrapping on overflow.
+    /// ## Panics
+    /// Panics if `align` is not a power of two.
+    fn align_up(self, align: usize) -> Self;
+    /// Rounds the address down to a multiple of `align`.
+    /// ## Panics
+    /// Panics if `align` is not a power of two.
+    fn align_down(self, align: usize) -> Self;
+    /// Returns if the address is a multiple of `align`.
+    /// ## Panics
+    /// Panics if `align` is not a power of two.
+    fn is_aligned_by(self, align: usize) -> bool;
+    /// Returns the number of bytes from `self` to `other`, negative if `other` comes first.
+    fn distance_to(self, other: Self) -> isize;
+    /// Offsets the pointer by `count` values, or returns `None` if the address would wrap around.
+    fn checked_add(self, count: usize) -> Option<Self>;
+    /// Offsets the pointer back by `count` values, or returns `None` if the address would wrap around.
+    fn checked_sub(self, count: usize) -> Option<Self>;
+    /// Offsets the pointer by `bytes`, or returns `None` if the address would wrap around.
+    fn checked_byte_add(self, bytes: usize) -> Option<Self>;
+    /// Offsets the pointer back by `bytes`, or returns `None` if the address would wrap around.
+    fn checked_byte_sub(self, bytes: usize) -> Option<Self>;
+    /// Casts the pointer to a pointer to bytes.
+    fn cast_bytes(self) -> Self::Bytes;
+    /// Returns an iterator over the pointers from `self` up to, but not
+    /// including, `end`, stepping `stride` bytes at a time.
+    /// ## Panics
+    /// Panics if `stride` is zero.
+    fn stride_to(self, end: Self, stride: usize) -> Stride<Self>;
+}
+
+/// An iterator over pointers in a `[start, end)` range, by a fixed stride in bytes.
+///
+/// Created by [`PtrExt::stride_to`].
+#[derive(Debug, Clone)]
+pub struct Stride<P> {
+    next: P,
+    end: P,
+    stride: usize,
+}
+
+/// Returns the mask of the low bits that must be clear for `align`.
+#[inline]
+#[track_caller]
+fn align_mask(align: usize) -> usize {
+    assert!(align.is_power_of_two(), "align must be a power of two");
+    align - 1
+}
+
+macro_rules! impl_ptr_ext {
+    ($ptr:ident, $bytes:ty) => {
+        impl<T> PtrExt for *$ptr T {
+            type Bytes = $bytes;
+
+            #[inline]
+            fn align_up(self, align: usize) -> Self {
+                let mask = align_mask(align);
+                self.map_addr(|a| a.wrapping_add(mask) & !mask)
+            }
+            #[inline]
+            fn align_down(self, align: usize) -> Self {
+                let mask = align_mask(align);
+                self.map_addr(|a| a & !mask)
+            }
+            #[inline]
+            fn is_aligned_by(self, align: usize) -> bool {
+                self.addr() & align_mask(align) == 0
+            }
+            #[inline]
+            fn distance_to(self, other: Self) -> isize {
+                other.addr().wrapping_sub(self.addr()) as isize
+            }
+            #[inline]
+            fn checked_add(self, count: usize) -> Option<Self> {
+                self.checked_byte_add(count.checked_mul(core::mem::size_of::<T>())?)
+            }
+            #[inline]
+            fn checked_sub(self, count: usize) -> Option<Self> {
+                self.checked_byte_sub(count.checked_mul(core::mem::size_of::<T>())?)
+            }
+            #[inline]
+            fn checked_byte_add(self, bytes: usize) -> Option<Self> {
+                self.addr().checked_add(bytes)?;
+                Some(self.wrapping_byte_add(bytes))
+            }
+            #[inline]
+            fn checked_byte_sub(self, bytes: usize) -> Option<Self> {
+                self.addr().checked_sub(bytes)?;
+                Some(self.wrapping_byte_sub(bytes))
+            }
+            #[inline]
+            fn cast_bytes(self) -> Self::Bytes {
+                self.cast()
+            }
+            #[inline]
+            fn stride_to(self, end: Self, stride: usize) -> Stride<Self> {
+                assert!(stride != 0, "stride must not be zero");
+                Stride { next: self, end, stride }
+            }
+        }
+
+        impl<T> Iterator for Stride<*$ptr T> {
+            type Item = *$ptr T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.next.addr() >= self.end.addr() {
+                    return None;
+                }
+                let res = self.next;
+                self.next = match self.next.checked_byte_add(self.stride) {
+                    Some(next) => next,
+                    None => self.end,
+                };
+                Some(res)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.end.addr().saturating_sub(self.next.addr()).div_ceil(self.stride);
+                (len, Some(len))
+            }
+        }
+
+        impl<T> ExactSizeIterator for Stride<*$ptr T> {}
+        impl<T> core::iter::FusedIterator for Stride<*$ptr T> {}
+    };
+}
+
+impl_ptr_ext!(const, *const u8);
+impl_ptr_ext!(mut, *mut u8);
+
 /// A trait for objects that can be created from a raw pointer.
 pub trait FromRawPointer<T> {
     /// Creates an object from a raw pointer.
+    /// ## Safety
+    /// `ptr` must point to a valid `T`, and the returned object
+    /// must not be dropped while the original is still in use.
     unsafe fn from_raw_pointer(ptr: *mut T) -> T;
+    /// Creates an object from a raw pointer, using `alloc` for the intermediate copy.
+    /// ## Safety
+    /// See [`FromRawPointer::from_raw_pointer`].
+    unsafe fn from_raw_pointer_in<A: crate::RawAllocator>(ptr: *mut T, alloc: A) -> T;
+    /// Creates an object from a raw pointer,
+    /// returning an error if the intermediate copy could not be allocated.
+    /// ## Safety
+    /// See [`FromRawPointer::from_raw_pointer`].
+    unsafe fn try_from_raw_pointer(ptr: *mut T) -> Result<T, crate::AllocError>;
+    /// Creates an object from a raw pointer, using `alloc` for the intermediate copy,
+    /// returning an error if it could not be allocated.
+    /// ## Safety
+    /// See [`FromRawPointer::from_raw_pointer`].
+    unsafe fn try_from_raw_pointer_in<A: crate::RawAllocator>(ptr: *mut T, alloc: A) -> Result<T, crate::AllocError>;
 }
 
 /// A trait that allows objects to be cloned by copying bytes.
 pub trait ByteClone {
     /// Clones the object by copying bytes.
+    /// ## Safety
+    /// The clone shares any memory the original owns, so dropping
+    /// both of them is a double free unless `Self` has no drop glue.
     unsafe fn byte_clone(&self) -> Self;
+    /// Clones the object by copying bytes, using `alloc` for the intermediate copy.
+    /// ## Safety
+    /// See [`ByteClone::byte_clone`].
+    unsafe fn byte_clone_in<A: crate::RawAllocator>(&self, alloc: A) -> Self;
+    /// Clones the object by copying bytes,
+    /// returning an error if the intermediate copy could not be allocated.
+    /// ## Safety
+    /// See [`ByteClone::byte_clone`].
+    unsafe fn try_byte_clone(&self) -> Result<Self, crate::AllocError> where Self: Sized;
+    /// Clones the object by copying bytes, using `alloc` for the intermediate copy,
+    /// returning an error if it could not be allocated.
+    /// ## Safety
+    /// See [`ByteClone::byte_clone`].
+    unsafe fn try_byte_clone_in<A: crate::RawAllocator>(&self, alloc: A) -> Result<Self, crate::AllocError> where Self: Sized;
+    /// Copies the bytes of the object into a [`ShallowCopy`](crate::ShallowCopy),
+    /// which can be inspected but is never dropped.
+    fn shallow_clone(&self) -> crate::ShallowCopy<'_, Self> where Self: Sized + core::marker::Freeze;
 }
 
 impl<T> FromRawPointer<T> for T {
     unsafe fn from_raw_pointer(ptr: *mut T) -> T {
+        Self::from_raw_pointer_in(ptr, crate::Global)
+    }
+    unsafe fn from_raw_pointer_in<A: crate::RawAllocator>(ptr: *mut T, alloc: A) -> T {
         let original = crate::ByteObject::from(ptr);
         assert!(original.len() > 0);
-        let res: T = original.as_object();
+        let res: T = original.as_object_in(alloc);
         return res;
     }
+    unsafe fn try_from_raw_pointer(ptr: *mut T) -> Result<T, crate::AllocError> {
+        Self::try_from_raw_pointer_in(ptr, crate::Global)
+    }
+    unsafe fn try_from_raw_pointer_in<A: crate::RawAllocator>(ptr: *mut T, alloc: A) -> Result<T, crate::AllocError> {
+        if core::mem::size_of::<T>() == 0 {
+            return Ok(core::ptr::read(ptr));
+        }
+        crate::ByteObject::from(ptr).try_as_object_in(alloc)
+    }
 }
 
 impl<T> PointerUtils<T> for T {
@@ -40,9 +227,24 @@ impl<T> PointerUtils<T> for T {
 
 impl<T> ByteClone for T {
     unsafe fn byte_clone(&self) -> Self {
+        self.byte_clone_in(crate::Global)
+    }
+    unsafe fn byte_clone_in<A: crate::RawAllocator>(&self, alloc: A) -> Self {
         let original = crate::ByteObject::from(self);
         assert!(original.len() > 0);
-        let res = original.as_object();
+        let res = original.as_object_in(alloc);
         res
     }
+    unsafe fn try_byte_clone(&self) -> Result<Self, crate::AllocError> {
+        self.try_byte_clone_in(crate::Global)
+    }
+    unsafe fn try_byte_clone_in<A: crate::RawAllocator>(&self, alloc: A) -> Result<Self, crate::AllocError> {
+        if core::mem::size_of::<T>() == 0 {
+            return Ok(core::ptr::read(self));
+        }
+        crate::ByteObject::from(self).try_as_object_in(alloc)
+    }
+    fn shallow_clone(&self) -> crate::ShallowCopy<'_, Self> where Self: core::marker::Freeze {
+        crate::ShallowCopy::new(self)
+    }
 }
@@ -0,0 +1,74 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Reads the memory mappings of the current process from `/proc/self/maps`.
+
+/// Returns if `len` bytes at `addr` are mapped readable, and writable if `write` is set.
+///
+/// Returns `true` if the mappings cannot be read, so that a missing
+/// `/proc` never rejects a valid pointer.
+pub(crate) fn is_mapped(addr: usize, len: usize, write: bool) -> bool {
+    let Ok(maps) = std::fs::read_to_string("/proc/self/maps") else {
+        return true;
+    };
+    covers(&maps, addr, len, write)
+}
+
+/// Returns if the mappings listed in `maps` cover `addr..addr + len`.
+fn covers(maps: &str, addr: usize, len: usize, write: bool) -> bool {
+    let Some(end) = addr.checked_add(len) else {
+        return false;
+    };
+    let mut cursor = addr;
+    for line in maps.lines() {
+        let Some((start, stop, perms)) = parse_line(line) else {
+            continue;
+        };
+        if stop <= cursor {
+            continue;
+        }
+        if start > cursor {
+            return false;
+        }
+        let perms = perms.as_bytes();
+        if perms.first() != Some(&b'r') || (write && perms.get(1) != Some(&b'w')) {
+            return false;
+        }
+        cursor = stop;
+        if cursor >= end {
+            return true;
+        }
+    }
+    len == 0
+}
+
+/// Parses the address range and permissions of a line such as
+/// `7ffd1000-7ffd2000 rw-p 00000000 00:00 0 [stack]`.
+fn parse_line(line: &str) -> Option<(usize, usize, &str)> {
+    let mut parts = line.split_ascii_whitespace();
+    let (start, stop) = parts.next()?.split_once('-')?;
+    let perms = parts.next()?;
+    Some((usize::from_str_radix(start, 16).ok()?, usize::from_str_radix(stop, 16).ok()?, perms))
+}
@@ -0,0 +1,346 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Relative pointers for position-independent data.
+//!
+//! A [`RelPtr`] stores the distance from its own address to its target, so a
+//! block of memory that holds both can be copied, mapped or snapshotted at
+//! another address and still point at the right places.
+
+use core::marker::PhantomData;
+use core::{fmt, ptr};
+
+use crate::{FromBytes, NoUninit, Pod, Zeroable};
+
+/// A signed integer a [`RelPtr`] can store its offset in.
+/// ## Safety
+/// The implementing type must be a primitive signed integer.
+pub unsafe trait RelOffset: Pod + Copy + Eq + fmt::Debug {
+    /// Converts an offset in bytes, returning `None` if it does not fit.
+    fn from_isize(offset: isize) -> Option<Self>;
+
+    /// Converts back to an offset in bytes.
+    fn to_isize(self) -> isize;
+}
+
+macro_rules! impl_rel_offset {
+    ($($t:ty)*) => {$(
+        unsafe impl RelOffset for $t {
+            #[inline]
+            fn from_isize(offset: isize) -> Option<Self> {
+                <$t>::try_from(offset).ok()
+            }
+
+            #[inline]
+            fn to_isize(self) -> isize {
+                self as isize
+            }
+        }
+    )*};
+}
+
+impl_rel_offset! { i8 i16 i32 i64 isize }
+
+/// The error returned when a relative pointer cannot point at a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelPtrError {
+    /// The distance to the target does not fit in the offset type.
+    Overflow {
+        /// The distance, in bytes.
+        offset: isize,
+    },
+    /// The target is the pointer itself, which is the null representation.
+    SelfReference,
+    /// The length of a slice does not fit in the offset type.
+    LengthOverflow {
+        /// The length of the slice.
+        len: usize,
+    },
+}
+
+impl fmt::Display for RelPtrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelPtrError::Overflow { offset } => write!(f, "offset {offset} does not fit in the relative pointer"),
+            RelPtrError::SelfReference => write!(f, "a relative pointer cannot point at itself"),
+            RelPtrError::LengthOverflow { len } => write!(f, "length {len} does not fit in the relative slice"),
+        }
+    }
+}
+
+impl core::error::Error for RelPtrError {}
+
+/// A pointer to `T` stored as a signed offset from its own address.
+///
+/// An offset of zero is null, so a `RelPtr` cannot point at itself, and a
+/// zeroed `RelPtr` is null. Moving a `RelPtr` without its target breaks it,
+/// which is why it is neither `Clone` nor `Copy`.
+#[repr(transparent)]
+pub struct RelPtr<T, I: RelOffset = i32> {
+    offset: I,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T, I: RelOffset> RelPtr<T, I> {
+    /// Creates a null pointer.
+    #[inline]
+    #[must_use]
+    pub fn null() -> Self {
+        Self { offset: I::zeroed(), _marker: PhantomData }
+    }
+
+    /// Returns if the pointer is null.
+    #[inline]
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        self.offset == I::zeroed()
+    }
+
+    /// Returns the stored offset, in bytes.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> I {
+        self.offset
+    }
+
+    /// Makes the pointer null.
+    #[inline]
+    pub fn set_null(&mut self) {
+        self.offset = I::zeroed();
+    }
+
+    /// Points at `target`, or makes the pointer null if `target` is null.
+    /// ## Panics
+    /// Panics if the distance to `target` does not fit in `I`, or if `target` is the pointer itself.
+    #[inline]
+    #[track_caller]
+    pub fn set(&mut self, target: *const T) {
+        if let Err(err) = self.try_set(target) {
+            panic!("{err}");
+        }
+    }
+
+    /// Points at `target`, or makes the pointer null if `target` is null.
+    ///
+    /// Returns an error, leaving the pointer untouched, if the target cannot be represented.
+    pub fn try_set(&mut self, target: *const T) -> Result<(), RelPtrError> {
+        if target.is_null() {
+            self.set_null();
+            return Ok(());
+        }
+        let offset = (target.addr() as isize).wrapping_sub(self.base().addr() as isize);
+        if offset == 0 {
+            return Err(RelPtrError::SelfReference);
+        }
+        self.offset = I::from_isize(offset).ok_or(RelPtrError::Overflow { offset })?;
+        Ok(())
+    }
+
+    /// Returns the target, or null.
+    ///
+    /// The pointer keeps the provenance of `self`, so it is only usable if
+    /// the target lives in the same allocation as the `RelPtr`.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        if self.is_null() {
+            return ptr::null();
+        }
+        self.base().wrapping_byte_offset(self.offset.to_isize()).cast()
+    }
+
+    /// Returns the target, or null, for writing.
+    ///
+    /// The pointer keeps the provenance of `self`, so it is only usable if
+    /// the target lives in the same allocation as the `RelPtr`.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        if self.is_null() {
+            return ptr::null_mut();
+        }
+        (self as *mut Self).wrapping_byte_offset(self.offset.to_isize()).cast()
+    }
+
+    /// Returns a reference to the target, or `None` if the pointer is null.
+    /// ## Safety
+    /// The target must be a valid `T` in the same allocation as `self`,
+    /// and must not be written to while the reference lives.
+    #[inline]
+    pub unsafe fn get(&self) -> Option<&T> {
+        self.as_ptr().as_ref()
+    }
+
+    /// Returns a mutable reference to the target, or `None` if the pointer is null.
+    /// ## Safety
+    /// The target must be a valid `T` in the same allocation as `self`,
+    /// and must not be accessed through anything else while the reference lives.
+    #[inline]
+    pub unsafe fn get_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_ptr().as_mut()
+    }
+
+    /// Returns the address offsets are measured from.
+    #[inline]
+    fn base(&self) -> *const Self {
+        self
+    }
+}
+
+impl<T, I: RelOffset> Default for RelPtr<T, I> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T, I: RelOffset> fmt::Debug for RelPtr<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelPtr").field("offset", &self.offset).field("ptr", &self.as_ptr()).finish()
+    }
+}
+
+unsafe impl<T, I: RelOffset> Zeroable for RelPtr<T, I> {}
+unsafe impl<T, I: RelOffset> FromBytes for RelPtr<T, I> {}
+unsafe impl<T, I: RelOffset> NoUninit for RelPtr<T, I> {}
+unsafe impl<T, I: RelOffset> Pod for RelPtr<T, I> {}
+
+/// A slice of `T` stored as a [`RelPtr`] to its first element and a length.
+///
+/// Empty slices are stored as a null pointer.
+#[repr(C)]
+pub struct RelSlice<T, I: RelOffset = i32> {
+    ptr: RelPtr<T, I>,
+    len: I,
+}
+
+impl<T, I: RelOffset> RelSlice<T, I> {
+    /// Creates an empty slice.
+    #[inline]
+    #[must_use]
+    pub fn empty() -> Self {
+        Self { ptr: RelPtr::null(), len: I::zeroed() }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        // A length read from arbitrary bytes may be negative.
+        usize::try_from(self.len.to_isize()).unwrap_or(0)
+    }
+
+    /// Returns if the slice has no elements.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Points at `target`.
+    /// ## Panics
+    /// Panics if the distance to `target` or its length does not fit in `I`.
+    #[inline]
+    #[track_caller]
+    pub fn set(&mut self, target: *const [T]) {
+        if let Err(err) = self.try_set(target) {
+            panic!("{err}");
+        }
+    }
+
+    /// Points at `target`.
+    ///
+    /// Returns an error, leaving the slice untouched, if the target cannot be represented.
+    pub fn try_set(&mut self, target: *const [T]) -> Result<(), RelPtrError> {
+        let len = target.len();
+        if len == 0 {
+            *self = Self::empty();
+            return Ok(());
+        }
+        let stored_len = I::from_isize(len as isize)
+            .filter(|_| len <= isize::MAX as usize)
+            .ok_or(RelPtrError::LengthOverflow { len })?;
+        self.ptr.try_set(target.cast())?;
+        self.len = stored_len;
+        Ok(())
+    }
+
+    /// Returns the slice as a raw pointer.
+    ///
+    /// The pointer keeps the provenance of `self`, so it is only usable if
+    /// the elements live in the same allocation as the `RelSlice`.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const [T] {
+        let ptr = if self.ptr.is_null() { ptr::NonNull::dangling().as_ptr() } else { self.ptr.as_ptr() };
+        ptr::slice_from_raw_parts(ptr, self.len())
+    }
+
+    /// Returns the slice as a raw pointer for writing.
+    ///
+    /// The pointer keeps the provenance of `self`, so it is only usable if
+    /// the elements live in the same allocation as the `RelSlice`.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut [T] {
+        let len = self.len();
+        let ptr = if self.ptr.is_null() { ptr::NonNull::dangling().as_ptr() } else { self.ptr.as_mut_ptr() };
+        ptr::slice_from_raw_parts_mut(ptr, len)
+    }
+
+    /// Returns the elements.
+    /// ## Safety
+    /// The elements must be valid values of `T` in the same allocation as `self`,
+    /// and must not be written to while the slice lives.
+    #[inline]
+    pub unsafe fn get(&self) -> &[T] {
+        &*self.as_ptr()
+    }
+
+    /// Returns the elements for writing.
+    /// ## Safety
+    /// The elements must be valid values of `T` in the same allocation as `self`,
+    /// and must not be accessed through anything else while the slice lives.
+    #[inline]
+    pub unsafe fn get_mut(&mut self) -> &mut [T] {
+        &mut *self.as_mut_ptr()
+    }
+}
+
+impl<T, I: RelOffset> Default for RelSlice<T, I> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T, I: RelOffset> fmt::Debug for RelSlice<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelSlice").field("ptr", &self.ptr).field("len", &self.len).finish()
+    }
+}
+
+unsafe impl<T, I: RelOffset> Zeroable for RelSlice<T, I> {}
+unsafe impl<T, I: RelOffset> FromBytes for RelSlice<T, I> {}
+unsafe impl<T, I: RelOffset> NoUninit for RelSlice<T, I> {}
+unsafe impl<T, I: RelOffset> Pod for RelSlice<T, I> {}
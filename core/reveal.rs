@@ -0,0 +1,72 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Sanctioned access to private fields.
+
+/// A struct with a public twin that has the same layout.
+///
+/// Implement it with `#[derive(Reveal)]`, which declares a `{Name}Revealed`
+/// struct with the same fields, all public, and the same `repr`.
+/// Revealing is a pointer cast, so it costs nothing.
+/// ## Safety
+/// `Self::Revealed` must have the same size, alignment and field offsets as
+/// `Self`, and every value of either type must be a valid value of the other.
+pub unsafe trait Reveal: Sized {
+    /// The twin type with public fields.
+    type Revealed;
+
+    /// Fails to evaluate if the layouts of `Self` and `Self::Revealed` differ.
+    /// Set by `#[derive(Reveal)]` for generic structs.
+    #[doc(hidden)]
+    const LAYOUT_CHECK: () = ();
+
+    /// Returns the value with its fields made public.
+    #[inline]
+    fn reveal(&self) -> &Self::Revealed {
+        let () = Self::LAYOUT_CHECK;
+        unsafe { crate::transmute_ref(self) }
+    }
+
+    /// Returns the value with its fields made public, for modification.
+    #[inline]
+    fn reveal_mut(&mut self) -> &mut Self::Revealed {
+        let () = Self::LAYOUT_CHECK;
+        unsafe { crate::transmute_mut(self) }
+    }
+
+    /// Turns the value into its twin.
+    #[inline]
+    fn into_revealed(self) -> Self::Revealed {
+        let () = Self::LAYOUT_CHECK;
+        unsafe { crate::transmute_checked(self) }
+    }
+
+    /// Turns a twin back into the value.
+    #[inline]
+    fn from_revealed(revealed: Self::Revealed) -> Self {
+        let () = Self::LAYOUT_CHECK;
+        unsafe { crate::transmute_checked(revealed) }
+    }
+}
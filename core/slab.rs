@@ -0,0 +1,496 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! A typed slab allocator.
+
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::{fmt, mem};
+
+use crate::{AllocError, Global, Layout, RawAllocator, SpinLock};
+
+/// The header at the start of every page of a [`Slab`].
+struct Page {
+    next: *mut Page,
+    prev_partial: *mut Page,
+    next_partial: *mut Page,
+    free: *mut u8,
+    used: usize,
+}
+
+/// The mutable state of a [`Slab`].
+struct SlabState {
+    pages: *mut Page,
+    partial: *mut Page,
+    page_count: usize,
+    used: usize,
+}
+
+unsafe impl Send for SlabState {}
+
+/// Occupancy statistics of a [`Slab`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlabStats {
+    /// The number of pages the slab holds.
+    pub pages: usize,
+    /// The number of slots in those pages.
+    pub slots: usize,
+    /// The number of slots in use.
+    pub used: usize,
+    /// The size of a slot, in bytes.
+    pub slot_size: usize,
+}
+
+impl SlabStats {
+    /// Returns the number of free slots.
+    #[inline]
+    #[must_use]
+    pub fn free(&self) -> usize {
+        self.slots - self.used
+    }
+
+    /// Returns the fraction of slots in use, from `0.0` to `1.0`.
+    #[must_use]
+    pub fn occupancy(&self) -> f64 {
+        if self.slots == 0 {
+            return 0.0;
+        }
+        self.used as f64 / self.slots as f64
+    }
+}
+
+/// An allocator for many values of one type.
+///
+/// Pages of `page_size` bytes are requested from the backing allocator and
+/// carved into slots, and every page keeps its own list of free slots.
+/// Values are handed out as [`SlabBox`]es that give their slot back when dropped.
+///
+/// A slab made with [`with_constructor`](Slab::with_constructor) caches
+/// constructed values: every slot of a new page is constructed once, a
+/// dropped `SlabBox` leaves its value in the slot for the next
+/// [`alloc_cached`](Slab::alloc_cached), and values are only dropped when
+/// their page is given back. A [`with_reset`](Slab::with_reset) hook can
+/// clean up a value as its slot is returned.
+pub struct Slab<T, A: RawAllocator = Global> {
+    state: SpinLock<SlabState>,
+    page_size: usize,
+    constructor: Option<fn() -> T>,
+    reset: Option<fn(&mut T)>,
+    alloc: A,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send, A: RawAllocator + Send> Send for Slab<T, A> {}
+unsafe impl<T: Send, A: RawAllocator + Sync> Sync for Slab<T, A> {}
+
+impl<T> Slab<T> {
+    /// Creates a slab that requests its pages from [`malloc`](crate::malloc).
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: RawAllocator> Slab<T, A> {
+    /// The page size of a new slab.
+    pub const DEFAULT_PAGE_SIZE: usize = 4096;
+
+    /// Creates a slab that requests its pages from `alloc`.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self {
+            state: SpinLock::new(SlabState { pages: ptr::null_mut(), partial: ptr::null_mut(), page_count: 0, used: 0 }),
+            page_size: Self::DEFAULT_PAGE_SIZE,
+            constructor: None,
+            reset: None,
+            alloc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the size of the pages the slab requests.
+    /// ## Panics
+    /// Panics if `page_size` is not a power of two or cannot hold a single slot.
+    #[must_use]
+    #[track_caller]
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        assert!(page_size.is_power_of_two(), "the page size must be a power of two");
+        self.page_size = page_size;
+        assert!(self.slots_per_page() > 0, "the page size cannot hold a single slot");
+        self
+    }
+
+    /// Makes the slab cache values built by `constructor`.
+    #[inline]
+    #[must_use]
+    pub fn with_constructor(mut self, constructor: fn() -> T) -> Self {
+        self.constructor = Some(constructor);
+        self
+    }
+
+    /// Makes a caching slab run `reset` on a value whenever its slot is returned,
+    /// so that [`alloc_cached`](Slab::alloc_cached) always hands out a clean value.
+    ///
+    /// Without it, cached values keep whatever state they were returned in
+    /// and are only dropped when their page is given back.
+    #[inline]
+    #[must_use]
+    pub fn with_reset(mut self, reset: fn(&mut T)) -> Self {
+        self.reset = Some(reset);
+        self
+    }
+
+    /// Returns the size of the pages the slab requests.
+    #[inline]
+    #[must_use]
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Returns occupancy statistics.
+    #[must_use]
+    pub fn stats(&self) -> SlabStats {
+        let state = self.state.lock();
+        SlabStats {
+            pages: state.page_count,
+            slots: state.page_count * self.slots_per_page(),
+            used: state.used,
+            slot_size: Self::slot_layout().0.size(),
+        }
+    }
+
+    /// Moves `value` into a free slot.
+    ///
+    /// In a caching slab the value replaces, and drops, the cached one.
+    #[inline]
+    pub fn alloc(&self, value: T) -> SlabBox<'_, T, A> {
+        self.try_alloc(value).unwrap_or_else(|e| e.handle())
+    }
+
+    /// Moves `value` into a free slot, returning an error if no page can be requested.
+    pub fn try_alloc(&self, value: T) -> Result<SlabBox<'_, T, A>, AllocError> {
+        let ptr = self.take_slot()?;
+        unsafe {
+            if self.constructor.is_some() {
+                *ptr.as_ptr() = value;
+            } else {
+                ptr.write(value);
+            }
+        }
+        Ok(SlabBox { ptr, slab: self })
+    }
+
+    /// Takes a slot holding a cached value.
+    /// ## Panics
+    /// Panics if the slab was not made with [`with_constructor`](Slab::with_constructor).
+    #[inline]
+    #[track_caller]
+    pub fn alloc_cached(&self) -> SlabBox<'_, T, A> {
+        self.try_alloc_cached().unwrap_or_else(|e| e.handle())
+    }
+
+    /// Takes a slot holding a cached value, returning an error if no page can be requested.
+    /// ## Panics
+    /// Panics if the slab was not made with [`with_constructor`](Slab::with_constructor).
+    #[track_caller]
+    pub fn try_alloc_cached(&self) -> Result<SlabBox<'_, T, A>, AllocError> {
+        assert!(self.constructor.is_some(), "the slab has no constructor to cache values with");
+        Ok(SlabBox { ptr: self.take_slot()?, slab: self })
+    }
+
+    /// Gives every page without values in use back to the backing allocator.
+    pub fn shrink(&self) {
+        let mut state = self.state.lock();
+        let mut prev: *mut Page = ptr::null_mut();
+        let mut page = state.pages;
+        unsafe {
+            while !page.is_null() {
+                let next = (*page).next;
+                if (*page).used == 0 {
+                    if prev.is_null() {
+                        state.pages = next;
+                    } else {
+                        (*prev).next = next;
+                    }
+                    Self::unlink_partial(&mut state, page);
+                    state.page_count -= 1;
+                    self.free_page(page);
+                } else {
+                    prev = page;
+                }
+                page = next;
+            }
+        }
+    }
+
+    /// Returns the layout of a slot and the offset of the value in it.
+    #[inline]
+    fn slot_layout() -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<*mut u8>().extend(Layout::new::<T>()).unwrap();
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Returns the offset of the first slot in a page.
+    #[inline]
+    fn first_slot() -> usize {
+        mem::size_of::<Page>().next_multiple_of(Self::slot_layout().0.align())
+    }
+
+    /// Returns the number of slots in a page.
+    #[inline]
+    fn slots_per_page(&self) -> usize {
+        let align = Self::slot_layout().0.align().max(mem::align_of::<Page>());
+        if self.page_size < align {
+            return 0;
+        }
+        self.page_size.saturating_sub(Self::first_slot()) / Self::slot_layout().0.size()
+    }
+
+    /// Returns the layout of a page.
+    #[inline]
+    fn page_layout(&self) -> Layout {
+        Layout::from_size_align(self.page_size, self.page_size).unwrap()
+    }
+
+    /// Takes a free slot and returns a pointer to its value.
+    fn take_slot(&self) -> Result<NonNull<T>, AllocError> {
+        let mut state = self.state.lock();
+        if state.partial.is_null() {
+            // The constructor may panic or use the slab itself, so the
+            // page is built without holding the lock.
+            drop(state);
+            let page = self.new_page()?;
+            state = self.state.lock();
+            unsafe {
+                (*page).next = state.pages;
+                state.pages = page;
+                state.page_count += 1;
+                Self::push_partial(&mut state, page);
+            }
+        }
+        unsafe {
+            let page = state.partial;
+            let slot = (*page).free;
+            (*page).free = slot.cast::<*mut u8>().read();
+            (*page).used += 1;
+            state.used += 1;
+            if (*page).free.is_null() {
+                Self::unlink_partial(&mut state, page);
+            }
+            Ok(NonNull::new_unchecked(slot.wrapping_add(Self::slot_layout().1).cast()))
+        }
+    }
+
+    /// Gives back the slot holding the value at `ptr`.
+    unsafe fn return_slot(&self, ptr: NonNull<T>) {
+        let slot = ptr.as_ptr().cast::<u8>().wrapping_sub(Self::slot_layout().1);
+        let page = slot.map_addr(|addr| addr & !(self.page_size - 1)).cast::<Page>();
+        let mut state = self.state.lock();
+        let was_full = (*page).free.is_null();
+        slot.cast::<*mut u8>().write((*page).free);
+        (*page).free = slot;
+        (*page).used -= 1;
+        state.used -= 1;
+        if was_full {
+            Self::push_partial(&mut state, page);
+        }
+    }
+
+    /// Requests a page and carves it into slots, building their values in a caching slab.
+    #[cold]
+    fn new_page(&self) -> Result<*mut Page, AllocError> {
+        /// Drops the values built so far and frees the page if a constructor panics.
+        struct Guard<'s, T, A: RawAllocator> {
+            slab: &'s Slab<T, A>,
+            base: *mut u8,
+            built: usize,
+        }
+
+        impl<T, A: RawAllocator> Drop for Guard<'_, T, A> {
+            fn drop(&mut self) {
+                unsafe {
+                    for i in 0..self.built {
+                        ptr::drop_in_place(Slab::<T, A>::value_at(self.base, i));
+                    }
+                    self.slab.alloc.deallocate(self.base, self.slab.page_layout());
+                }
+            }
+        }
+
+        if self.slots_per_page() == 0 {
+            return Err(AllocError::CapacityOverflow);
+        }
+        let layout = self.page_layout();
+        let base = self.alloc.allocate(layout);
+        if base.is_null() {
+            return Err(AllocError::OutOfMemory(layout));
+        }
+        if let Some(constructor) = self.constructor {
+            let mut guard = Guard { slab: self, base, built: 0 };
+            while guard.built < self.slots_per_page() {
+                unsafe { Self::value_at(base, guard.built).write(constructor()) };
+                guard.built += 1;
+            }
+            mem::forget(guard);
+        }
+        let mut free = ptr::null_mut();
+        for i in (0..self.slots_per_page()).rev() {
+            let slot = base.wrapping_add(Self::first_slot() + i * Self::slot_layout().0.size());
+            unsafe { slot.cast::<*mut u8>().write(free) };
+            free = slot;
+        }
+        let page = base.cast::<Page>();
+        unsafe {
+            page.write(Page { next: ptr::null_mut(), prev_partial: ptr::null_mut(), next_partial: ptr::null_mut(), free, used: 0 });
+        }
+        Ok(page)
+    }
+
+    /// Returns a pointer to the value in slot `index` of the page at `base`.
+    #[inline]
+    fn value_at(base: *mut u8, index: usize) -> *mut T {
+        let (slot_layout, value_offset) = Self::slot_layout();
+        base.wrapping_add(Self::first_slot() + index * slot_layout.size() + value_offset).cast()
+    }
+
+    /// Drops the cached values of `page` and gives it back to the backing allocator.
+    unsafe fn free_page(&self, page: *mut Page) {
+        if self.constructor.is_some() {
+            for i in 0..self.slots_per_page() {
+                ptr::drop_in_place(Self::value_at(page.cast(), i));
+            }
+        }
+        self.alloc.deallocate(page.cast(), self.page_layout());
+    }
+
+    /// Adds `page` to the front of the partial list.
+    unsafe fn push_partial(state: &mut SlabState, page: *mut Page) {
+        (*page).prev_partial = ptr::null_mut();
+        (*page).next_partial = state.partial;
+        if !state.partial.is_null() {
+            (*state.partial).prev_partial = page;
+        }
+        state.partial = page;
+    }
+
+    /// Removes `page` from the partial list, if it is on it.
+    unsafe fn unlink_partial(state: &mut SlabState, page: *mut Page) {
+        let (prev, next) = ((*page).prev_partial, (*page).next_partial);
+        if !prev.is_null() {
+            (*prev).next_partial = next;
+        } else if state.partial == page {
+            state.partial = next;
+        } else {
+            return;
+        }
+        if !next.is_null() {
+            (*next).prev_partial = prev;
+        }
+        (*page).prev_partial = ptr::null_mut();
+        (*page).next_partial = ptr::null_mut();
+    }
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: RawAllocator> Drop for Slab<T, A> {
+    fn drop(&mut self) {
+        let mut page = self.state.get_mut().pages;
+        while !page.is_null() {
+            unsafe {
+                let next = (*page).next;
+                self.free_page(page);
+                page = next;
+            }
+        }
+    }
+}
+
+impl<T, A: RawAllocator> fmt::Debug for Slab<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slab")
+            .field("page_size", &self.page_size)
+            .field("cached", &self.constructor.is_some())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// A value in a slot of a [`Slab`], which gives the slot back when dropped.
+pub struct SlabBox<'a, T, A: RawAllocator = Global> {
+    ptr: NonNull<T>,
+    slab: &'a Slab<T, A>,
+}
+
+unsafe impl<T: Send, A: RawAllocator + Sync> Send for SlabBox<'_, T, A> {}
+unsafe impl<T: Sync, A: RawAllocator + Sync> Sync for SlabBox<'_, T, A> {}
+
+impl<T, A: RawAllocator> SlabBox<'_, T, A> {
+    /// Returns a pointer to the value.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(this: &Self) -> *mut T {
+        this.ptr.as_ptr()
+    }
+}
+
+impl<T, A: RawAllocator> Deref for SlabBox<'_, T, A> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T, A: RawAllocator> DerefMut for SlabBox<'_, T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T, A: RawAllocator> Drop for SlabBox<'_, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.slab.constructor.is_none() {
+                ptr::drop_in_place(self.ptr.as_ptr());
+            } else if let Some(reset) = self.slab.reset {
+                reset(self.ptr.as_mut());
+            }
+            self.slab.return_slot(self.ptr);
+        }
+    }
+}
+
+impl<T: fmt::Debug, A: RawAllocator> fmt::Debug for SlabBox<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
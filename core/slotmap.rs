@@ -0,0 +1,551 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Generational slot maps.
+
+use core::ops::{Index, IndexMut};
+use core::{fmt, iter, mem, slice};
+
+use crate::{DynArray, Global, RawAllocator};
+
+/// A key into a [`SlotMap`].
+///
+/// A handle stops being valid once its value is removed, even if the slot
+/// is reused for another value afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    index: u32,
+    generation: u32,
+}
+
+impl Handle {
+    /// Returns the index of the slot.
+    #[inline]
+    #[must_use]
+    pub const fn index(self) -> u32 {
+        self.index
+    }
+
+    /// Returns the generation of the slot the handle was made for.
+    #[inline]
+    #[must_use]
+    pub const fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// The contents of a [`SlotMap`] slot.
+enum Entry<T> {
+    Occupied(T),
+    Vacant { next_free: u32 },
+}
+
+/// A slot of a [`SlotMap`].
+struct Slot<T> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+/// The index that ends the free list.
+const NO_SLOT: u32 = u32::MAX;
+
+/// A table of values that hands out [`Handle`]s instead of references.
+///
+/// Inserting, removing and looking up are O(1). Removed slots are reused,
+/// but every reuse bumps the slot's generation, so handles to removed
+/// values never see the new ones. A slot whose generation would wrap around
+/// is retired instead of reused.
+pub struct SlotMap<T, A: RawAllocator = Global> {
+    slots: DynArray<Slot<T>, A>,
+    free_head: u32,
+    len: usize,
+}
+
+impl<T> SlotMap<T> {
+    /// Creates an empty slot map without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates an empty slot map with room for `capacity` values.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: DynArray::with_capacity(capacity), free_head: NO_SLOT, len: 0 }
+    }
+}
+
+impl<T, A: RawAllocator> SlotMap<T, A> {
+    /// Creates an empty slot map in `alloc` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self { slots: DynArray::new_in(alloc), free_head: NO_SLOT, len: 0 }
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns if there are no values.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of slots that can be used without reallocating.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Inserts `value` and returns its handle.
+    /// ## Panics
+    /// Panics if the map already has `u32::MAX` slots.
+    #[inline]
+    pub fn insert(&mut self, value: T) -> Handle {
+        self.insert_with(|_| value)
+    }
+
+    /// Inserts the value `f` builds from its own handle, and returns the handle.
+    /// ## Panics
+    /// Panics if the map already has `u32::MAX` slots.
+    pub fn insert_with<F: FnOnce(Handle) -> T>(&mut self, f: F) -> Handle {
+        if self.free_head != NO_SLOT {
+            let index = self.free_head;
+            let handle = Handle { index, generation: self.slots[index as usize].generation };
+            // Build the value first, so a panicking `f` leaves the slot on the free list.
+            let value = f(handle);
+            let slot = &mut self.slots[index as usize];
+            if let Entry::Vacant { next_free } = slot.entry {
+                self.free_head = next_free;
+            }
+            slot.entry = Entry::Occupied(value);
+            self.len += 1;
+            return handle;
+        }
+        let index = u32::try_from(self.slots.len()).ok().filter(|&index| index != NO_SLOT);
+        let handle = Handle { index: index.expect("slot map is full"), generation: 0 };
+        self.slots.push(Slot { generation: 0, entry: Entry::Occupied(f(handle)) });
+        self.len += 1;
+        handle
+    }
+
+    /// Removes the value of `handle` and returns it.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.get(handle)?;
+        let slot = &mut self.slots[handle.index as usize];
+        let retire = slot.generation == u32::MAX;
+        slot.generation = slot.generation.wrapping_add(1);
+        let next_free = if retire { NO_SLOT } else { self.free_head };
+        let entry = mem::replace(&mut slot.entry, Entry::Vacant { next_free });
+        if !retire {
+            self.free_head = handle.index;
+        }
+        self.len -= 1;
+        match entry {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    /// Returns if `handle` refers to a value.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Returns the value of `handle`.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        match self.slots.get(handle.index as usize) {
+            Some(Slot { generation, entry: Entry::Occupied(value) }) if *generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of `handle` for writing.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(Slot { generation, entry: Entry::Occupied(value) }) if *generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Keeps only the values for which `keep` returns `true`.
+    pub fn retain<F: FnMut(Handle, &mut T) -> bool>(&mut self, mut keep: F) {
+        for index in 0..self.slots.len() {
+            let slot = &mut self.slots[index];
+            let handle = Handle { index: index as u32, generation: slot.generation };
+            let remove = match &mut slot.entry {
+                Entry::Occupied(value) => !keep(handle, value),
+                Entry::Vacant { .. } => false,
+            };
+            if remove {
+                self.remove(handle);
+            }
+        }
+    }
+
+    /// Removes every value. Handles stay invalid after the slots are reused.
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Returns an iterator over the handles and values.
+    #[inline]
+    pub fn iter(&self) -> SlotMapIter<'_, T> {
+        SlotMapIter { slots: self.slots.iter().enumerate(), len: self.len }
+    }
+
+    /// Returns an iterator over the handles and values, for writing.
+    #[inline]
+    pub fn iter_mut(&mut self) -> SlotMapIterMut<'_, T> {
+        SlotMapIterMut { slots: self.slots.iter_mut().enumerate(), len: self.len }
+    }
+
+    /// Returns an iterator over the handles.
+    #[inline]
+    pub fn handles(&self) -> impl Iterator<Item = Handle> + '_ {
+        self.iter().map(|(handle, _)| handle)
+    }
+
+    /// Returns an iterator over the values.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the values, for writing.
+    #[inline]
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+}
+
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: RawAllocator> Index<Handle> for SlotMap<T, A> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, handle: Handle) -> &T {
+        self.get(handle).expect("invalid slot map handle")
+    }
+}
+
+impl<T, A: RawAllocator> IndexMut<Handle> for SlotMap<T, A> {
+    #[track_caller]
+    fn index_mut(&mut self, handle: Handle) -> &mut T {
+        self.get_mut(handle).expect("invalid slot map handle")
+    }
+}
+
+impl<T: fmt::Debug, A: RawAllocator> fmt::Debug for SlotMap<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, A: RawAllocator> IntoIterator for &'a SlotMap<T, A> {
+    type Item = (Handle, &'a T);
+    type IntoIter = SlotMapIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: RawAllocator> IntoIterator for &'a mut SlotMap<T, A> {
+    type Item = (Handle, &'a mut T);
+    type IntoIter = SlotMapIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the handles and values of a [`SlotMap`].
+pub struct SlotMapIter<'a, T> {
+    slots: iter::Enumerate<slice::Iter<'a, Slot<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for SlotMapIter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.slots.by_ref() {
+            if let Entry::Occupied(value) = &slot.entry {
+                self.len -= 1;
+                return Some((Handle { index: index as u32, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for SlotMapIter<'_, T> {}
+impl<T> iter::FusedIterator for SlotMapIter<'_, T> {}
+
+impl<T> fmt::Debug for SlotMapIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlotMapIter").field("len", &self.len).finish()
+    }
+}
+
+/// An iterator over the handles and values of a [`SlotMap`], for writing.
+pub struct SlotMapIterMut<'a, T> {
+    slots: iter::Enumerate<slice::IterMut<'a, Slot<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for SlotMapIterMut<'a, T> {
+    type Item = (Handle, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.slots.by_ref() {
+            if let Entry::Occupied(value) = &mut slot.entry {
+                self.len -= 1;
+                return Some((Handle { index: index as u32, generation: slot.generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for SlotMapIterMut<'_, T> {}
+impl<T> iter::FusedIterator for SlotMapIterMut<'_, T> {}
+
+impl<T> fmt::Debug for SlotMapIterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlotMapIterMut").field("len", &self.len).finish()
+    }
+}
+
+/// A map that attaches extra values to the [`Handle`]s of a [`SlotMap`].
+///
+/// Entries are stored by slot index, so lookups are O(1). An entry is only
+/// returned for the exact handle it was inserted with.
+pub struct SecondaryMap<T, A: RawAllocator = Global> {
+    slots: DynArray<Option<(u32, T)>, A>,
+    len: usize,
+}
+
+impl<T> SecondaryMap<T> {
+    /// Creates an empty map without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: RawAllocator> SecondaryMap<T, A> {
+    /// Creates an empty map in `alloc` without allocating.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self { slots: DynArray::new_in(alloc), len: 0 }
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns if there are no values.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Attaches `value` to `handle` and returns the value it replaces.
+    ///
+    /// An entry for a newer generation of the slot is kept, and `value` is dropped.
+    pub fn insert(&mut self, handle: Handle, value: T) -> Option<T> {
+        let index = handle.index as usize;
+        if index >= self.slots.len() {
+            self.slots.reserve(index + 1 - self.slots.len());
+            while self.slots.len() <= index {
+                self.slots.push(None);
+            }
+        }
+        let slot = &mut self.slots[index];
+        match slot {
+            Some((generation, _)) if *generation > handle.generation => None,
+            Some((generation, old)) if *generation == handle.generation => Some(mem::replace(old, value)),
+            _ => {
+                if slot.is_none() {
+                    self.len += 1;
+                }
+                *slot = Some((handle.generation, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the value attached to `handle` and returns it.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.get(handle)?;
+        self.len -= 1;
+        self.slots[handle.index as usize].take().map(|(_, value)| value)
+    }
+
+    /// Returns if a value is attached to `handle`.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// Returns the value attached to `handle`.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        match self.slots.get(handle.index as usize) {
+            Some(Some((generation, value))) if *generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value attached to `handle`, for writing.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(Some((generation, value))) if *generation == handle.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Removes every value.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the handles and values.
+    #[inline]
+    pub fn iter(&self) -> SecondaryMapIter<'_, T> {
+        SecondaryMapIter { slots: self.slots.iter().enumerate(), len: self.len }
+    }
+}
+
+impl<T> Default for SecondaryMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: RawAllocator> Index<Handle> for SecondaryMap<T, A> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, handle: Handle) -> &T {
+        self.get(handle).expect("no value for the handle")
+    }
+}
+
+impl<T, A: RawAllocator> IndexMut<Handle> for SecondaryMap<T, A> {
+    #[track_caller]
+    fn index_mut(&mut self, handle: Handle) -> &mut T {
+        self.get_mut(handle).expect("no value for the handle")
+    }
+}
+
+impl<T: fmt::Debug, A: RawAllocator> fmt::Debug for SecondaryMap<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, A: RawAllocator> IntoIterator for &'a SecondaryMap<T, A> {
+    type Item = (Handle, &'a T);
+    type IntoIter = SecondaryMapIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the handles and values of a [`SecondaryMap`].
+pub struct SecondaryMapIter<'a, T> {
+    slots: iter::Enumerate<slice::Iter<'a, Option<(u32, T)>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for SecondaryMapIter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in self.slots.by_ref() {
+            if let Some((generation, value)) = slot {
+                self.len -= 1;
+                return Some((Handle { index: index as u32, generation: *generation }, value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for SecondaryMapIter<'_, T> {}
+impl<T> iter::FusedIterator for SecondaryMapIter<'_, T> {}
+
+impl<T> fmt::Debug for SecondaryMapIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecondaryMapIter").field("len", &self.len).finish()
+    }
+}
@@ -0,0 +1,428 @@
+//
+// MIT License
+//
+// Copyright (c) 2022 AtomicGamer9523
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+
+
+//! Pointers that carry a tag in their spare bits.
+
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::{fmt, hash, mem, ptr};
+
+use bit_field::BitField;
+
+/// A pointer to `T` that stores a `BITS`-bit tag in its low bits.
+///
+/// The alignment of `T` must leave at least `BITS` zero bits at the bottom
+/// of every pointer; this is checked at compile time.
+/// The tag bits are set with [`BitField`], so the pointer keeps its provenance.
+pub struct TaggedPtr<T, const BITS: u32> {
+    raw: *mut T,
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T, const BITS: u32> TaggedPtr<T, BITS> {
+    /// The mask of the tag bits.
+    pub const MASK: usize = {
+        assert!(BITS > 0, "a tagged pointer needs at least one tag bit");
+        assert!(
+            BITS <= mem::align_of::<T>().trailing_zeros(),
+            "the alignment of the pointee does not leave enough spare bits for the tag"
+        );
+        (1 << BITS) - 1
+    };
+
+    /// Creates a tagged pointer from `ptr` and `tag`.
+    /// ## Panics
+    /// Panics if `ptr` is not aligned to `2^BITS` or if `tag` does not fit in `BITS` bits.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn new(ptr: *mut T, tag: usize) -> Self {
+        assert!(ptr.addr() & Self::MASK == 0, "pointer is not aligned enough to be tagged");
+        let mut res = Self { raw: ptr, _marker: PhantomData };
+        res.set_tag(tag);
+        res
+    }
+
+    /// Creates a null pointer with a tag of zero.
+    #[inline]
+    #[must_use]
+    pub const fn null() -> Self {
+        let _ = Self::MASK;
+        Self { raw: ptr::null_mut(), _marker: PhantomData }
+    }
+
+    /// Creates a tagged pointer from the value returned by [`into_raw`](TaggedPtr::into_raw).
+    #[inline]
+    #[must_use]
+    pub const fn from_raw(raw: *mut T) -> Self {
+        let _ = Self::MASK;
+        Self { raw, _marker: PhantomData }
+    }
+
+    /// Returns the pointer with the tag still in its low bits.
+    #[inline]
+    #[must_use]
+    pub const fn into_raw(self) -> *mut T {
+        self.raw
+    }
+
+    /// Returns the pointer without the tag.
+    #[inline]
+    #[must_use]
+    pub fn ptr(self) -> *mut T {
+        self.raw.map_addr(|addr| addr & !Self::MASK)
+    }
+
+    /// Returns if the pointer, ignoring the tag, is null.
+    #[inline]
+    #[must_use]
+    pub fn is_null(self) -> bool {
+        self.ptr().is_null()
+    }
+
+    /// Returns the tag.
+    #[inline]
+    #[must_use]
+    pub fn tag(self) -> usize {
+        self.raw.addr().get_bits(..BITS as usize)
+    }
+
+    /// Replaces the tag, keeping the pointer.
+    /// ## Panics
+    /// Panics if `tag` does not fit in `BITS` bits.
+    #[inline]
+    #[track_caller]
+    pub fn set_tag(&mut self, tag: usize) {
+        self.raw = self.raw.map_addr(|mut addr| *addr.set_bits(..BITS as usize, tag));
+    }
+
+    /// Returns a copy with the tag replaced.
+    /// ## Panics
+    /// Panics if `tag` does not fit in `BITS` bits.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn with_tag(mut self, tag: usize) -> Self {
+        self.set_tag(tag);
+        self
+    }
+
+    /// Replaces the pointer, keeping the tag.
+    /// ## Panics
+    /// Panics if `ptr` is not aligned to `2^BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn set_ptr(&mut self, ptr: *mut T) {
+        *self = Self::new(ptr, self.tag());
+    }
+
+    /// Returns bit `bit` of the tag.
+    /// ## Panics
+    /// Panics if `bit` is not smaller than `BITS`.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn tag_bit(self, bit: u32) -> bool {
+        assert!(bit < BITS, "tag bit out of range");
+        self.raw.addr().get_bit(bit as usize)
+    }
+
+    /// Sets bit `bit` of the tag to `value`.
+    /// ## Panics
+    /// Panics if `bit` is not smaller than `BITS`.
+    #[inline]
+    #[track_caller]
+    pub fn set_tag_bit(&mut self, bit: u32, value: bool) {
+        assert!(bit < BITS, "tag bit out of range");
+        self.raw = self.raw.map_addr(|mut addr| *addr.set_bit(bit as usize, value));
+    }
+}
+
+impl<T, const BITS: u32> Clone for TaggedPtr<T, BITS> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const BITS: u32> Copy for TaggedPtr<T, BITS> {}
+
+impl<T, const BITS: u32> PartialEq for TaggedPtr<T, BITS> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.raw, other.raw)
+    }
+}
+
+impl<T, const BITS: u32> Eq for TaggedPtr<T, BITS> {}
+
+impl<T, const BITS: u32> hash::Hash for TaggedPtr<T, BITS> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+
+impl<T, const BITS: u32> Default for TaggedPtr<T, BITS> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T, const BITS: u32> fmt::Debug for TaggedPtr<T, BITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedPtr").field("ptr", &self.ptr()).field("tag", &self.tag()).finish()
+    }
+}
+
+/// A [`TaggedPtr`] that can be shared between threads.
+///
+/// The pointer and its tag are always loaded and stored together.
+pub struct AtomicTaggedPtr<T, const BITS: u32> {
+    inner: AtomicPtr<T>,
+}
+
+impl<T, const BITS: u32> AtomicTaggedPtr<T, BITS> {
+    /// Creates an atomic tagged pointer holding `value`.
+    #[inline]
+    #[must_use]
+    pub const fn new(value: TaggedPtr<T, BITS>) -> Self {
+        Self { inner: AtomicPtr::new(value.into_raw()) }
+    }
+
+    /// Creates an atomic null pointer with a tag of zero.
+    #[inline]
+    #[must_use]
+    pub const fn null() -> Self {
+        Self::new(TaggedPtr::null())
+    }
+
+    /// Loads the pointer and its tag.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> TaggedPtr<T, BITS> {
+        TaggedPtr::from_raw(self.inner.load(order))
+    }
+
+    /// Stores `value`.
+    #[inline]
+    pub fn store(&self, value: TaggedPtr<T, BITS>, order: Ordering) {
+        self.inner.store(value.into_raw(), order)
+    }
+
+    /// Stores `value` and returns the previous value.
+    #[inline]
+    pub fn swap(&self, value: TaggedPtr<T, BITS>, order: Ordering) -> TaggedPtr<T, BITS> {
+        TaggedPtr::from_raw(self.inner.swap(value.into_raw(), order))
+    }
+
+    /// Stores `new` if the current value, tag included, is `current`.
+    ///
+    /// Returns the previous value, as `Ok` if it was replaced.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: TaggedPtr<T, BITS>,
+        new: TaggedPtr<T, BITS>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<TaggedPtr<T, BITS>, TaggedPtr<T, BITS>> {
+        self.inner
+            .compare_exchange(current.into_raw(), new.into_raw(), success, failure)
+            .map(TaggedPtr::from_raw)
+            .map_err(TaggedPtr::from_raw)
+    }
+
+    /// Like [`compare_exchange`](AtomicTaggedPtr::compare_exchange), but may fail spuriously.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: TaggedPtr<T, BITS>,
+        new: TaggedPtr<T, BITS>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<TaggedPtr<T, BITS>, TaggedPtr<T, BITS>> {
+        self.inner
+            .compare_exchange_weak(current.into_raw(), new.into_raw(), success, failure)
+            .map(TaggedPtr::from_raw)
+            .map_err(TaggedPtr::from_raw)
+    }
+
+    /// Replaces the tag, keeping the pointer, and returns the previous value.
+    /// ## Panics
+    /// Panics if `tag` does not fit in `BITS` bits.
+    #[track_caller]
+    pub fn fetch_set_tag(&self, tag: usize, order: Ordering) -> TaggedPtr<T, BITS> {
+        let fetch_order = match order {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            order => order,
+        };
+        let mut current = self.inner.load(fetch_order);
+        loop {
+            let new = TaggedPtr::<T, BITS>::from_raw(current).with_tag(tag).into_raw();
+            match self.inner.compare_exchange_weak(current, new, order, fetch_order) {
+                Ok(raw) => return TaggedPtr::from_raw(raw),
+                Err(raw) => current = raw,
+            }
+        }
+    }
+
+    /// Sets bit `bit` of the tag to `value` and returns the previous value.
+    /// ## Panics
+    /// Panics if `bit` is not smaller than `BITS`.
+    #[track_caller]
+    pub fn fetch_set_tag_bit(&self, bit: u32, value: bool, order: Ordering) -> TaggedPtr<T, BITS> {
+        assert!(bit < BITS, "tag bit out of range");
+        let raw = if value {
+            self.inner.fetch_or(1 << bit, order)
+        } else {
+            self.inner.fetch_and(!(1 << bit), order)
+        };
+        TaggedPtr::from_raw(raw)
+    }
+
+    /// Returns the value, consuming the atomic.
+    #[inline]
+    pub fn into_inner(self) -> TaggedPtr<T, BITS> {
+        TaggedPtr::from_raw(self.inner.into_inner())
+    }
+}
+
+impl<T, const BITS: u32> Default for AtomicTaggedPtr<T, BITS> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T, const BITS: u32> From<TaggedPtr<T, BITS>> for AtomicTaggedPtr<T, BITS> {
+    fn from(value: TaggedPtr<T, BITS>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, const BITS: u32> fmt::Debug for AtomicTaggedPtr<T, BITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+/// A pointer to `T` that stores a 16-bit tag in the unused high bits of an
+/// x86_64 canonical address.
+///
+/// Works with any alignment of `T`, but only for 48-bit virtual addresses.
+#[cfg(target_arch = "x86_64")]
+pub struct HighTaggedPtr<T> {
+    raw: *mut T,
+    _marker: PhantomData<*mut T>,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> HighTaggedPtr<T> {
+    /// The first bit of the tag.
+    const SHIFT: usize = 48;
+
+    /// Creates a tagged pointer from `ptr` and `tag`.
+    /// ## Panics
+    /// Panics if `ptr` is not a canonical 48-bit address.
+    #[inline]
+    #[must_use]
+    #[track_caller]
+    pub fn new(ptr: *mut T, tag: u16) -> Self {
+        assert!(Self::canonical(ptr.addr()) == ptr.addr(), "pointer is not a canonical 48-bit address");
+        Self { raw: ptr, _marker: PhantomData }.with_tag(tag)
+    }
+
+    /// Creates a null pointer with a tag of zero.
+    #[inline]
+    #[must_use]
+    pub const fn null() -> Self {
+        Self { raw: ptr::null_mut(), _marker: PhantomData }
+    }
+
+    /// Returns the pointer, sign-extended back to a canonical address.
+    #[inline]
+    #[must_use]
+    pub fn ptr(self) -> *mut T {
+        self.raw.map_addr(Self::canonical)
+    }
+
+    /// Returns the tag.
+    #[inline]
+    #[must_use]
+    pub fn tag(self) -> u16 {
+        self.raw.addr().get_bits(Self::SHIFT..) as u16
+    }
+
+    /// Replaces the tag, keeping the pointer.
+    #[inline]
+    pub fn set_tag(&mut self, tag: u16) {
+        self.raw = self.raw.map_addr(|mut addr| *addr.set_bits(Self::SHIFT.., tag as usize));
+    }
+
+    /// Returns a copy with the tag replaced.
+    #[inline]
+    #[must_use]
+    pub fn with_tag(mut self, tag: u16) -> Self {
+        self.set_tag(tag);
+        self
+    }
+
+    /// Sign-extends bit 47 of `addr` into the high bits.
+    #[inline]
+    fn canonical(addr: usize) -> usize {
+        (((addr << 16) as isize) >> 16) as usize
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> Clone for HighTaggedPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> Copy for HighTaggedPtr<T> {}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> PartialEq for HighTaggedPtr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.raw, other.raw)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> Eq for HighTaggedPtr<T> {}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> Default for HighTaggedPtr<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl<T> fmt::Debug for HighTaggedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HighTaggedPtr").field("ptr", &self.ptr()).field("tag", &self.tag()).finish()
+    }
+}
@@ -0,0 +1,71 @@
+//! Helpers shared by the derive macros.
+
+use alloc::format;
+use proc_macro2::TokenStream;
+
+/// Returns the `repr` hints of an item, such as `C`, `transparent` or `packed`.
+pub(crate) fn repr_hints(attrs: &[syn::Attribute]) -> syn::Result<syn::punctuated::Punctuated<syn::Meta, syn::Token![,]>> {
+    let mut hints = syn::punctuated::Punctuated::new();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        hints.extend(attr.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?);
+    }
+    Ok(hints)
+}
+
+/// Returns if the item has a `repr(C)` or `repr(transparent)` layout.
+pub(crate) fn has_stable_layout(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    Ok(repr_hints(attrs)?
+        .iter()
+        .any(|m| m.path().is_ident("C") || m.path().is_ident("transparent")))
+}
+
+/// Returns the fields of a struct, rejecting enums, unions and generic items.
+pub(crate) fn struct_fields<'a>(input: &'a syn::DeriveInput, derive: &str) -> syn::Result<&'a syn::Fields> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("#[derive({})] does not support generic types", derive),
+        ));
+    }
+    match &input.data {
+        syn::Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("#[derive({})] only supports structs", derive),
+        )),
+    }
+}
+
+/// Requires a `repr(C)` or `repr(transparent)` layout.
+pub(crate) fn require_stable_layout(input: &syn::DeriveInput, derive: &str) -> syn::Result<()> {
+    if has_stable_layout(&input.attrs)? {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        &input.ident,
+        format!("#[derive({})] requires #[repr(C)] or #[repr(transparent)]", derive),
+    ))
+}
+
+/// Asserts at compile time that every field type implements `bound`.
+pub(crate) fn assert_fields_impl(fields: &syn::Fields, bound: &TokenStream) -> TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+    quote::quote! {
+        const _: () = {
+            const fn __assert_field<T: ?Sized + #bound>() {}
+            #(__assert_field::<#types>();)*
+        };
+    }
+}
+
+/// Asserts at compile time that the struct has no padding bytes.
+pub(crate) fn assert_no_padding(name: &syn::Ident, fields: &syn::Fields, derive: &str) -> TokenStream {
+    let types = fields.iter().map(|f| &f.ty);
+    let message = format!("#[derive({})]: `{}` has padding bytes", derive, name);
+    quote::quote! {
+        const _: () = assert!(
+            ::core::mem::size_of::<#name>() == 0 #(+ ::core::mem::size_of::<#types>())*,
+            #message
+        );
+    }
+}
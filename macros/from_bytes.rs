@@ -0,0 +1,36 @@
+//! `#[derive(FromBytes)]` and `#[derive(AsBytes)]`.
+
+use proc_macro2::TokenStream;
+
+use crate::common;
+
+pub(crate) fn derive_from_bytes(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = common::struct_fields(&input, "FromBytes")?;
+    common::require_stable_layout(&input, "FromBytes")?;
+
+    let name = &input.ident;
+    let fields_are_from_bytes = common::assert_fields_impl(fields, &quote::quote!(::memutils::FromBytes));
+
+    Ok(quote::quote! {
+        #fields_are_from_bytes
+
+        unsafe impl ::memutils::Zeroable for #name {}
+        unsafe impl ::memutils::FromBytes for #name {}
+    })
+}
+
+pub(crate) fn derive_as_bytes(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = common::struct_fields(&input, "AsBytes")?;
+    common::require_stable_layout(&input, "AsBytes")?;
+
+    let name = &input.ident;
+    let fields_are_no_uninit = common::assert_fields_impl(fields, &quote::quote!(::memutils::NoUninit));
+    let no_padding = common::assert_no_padding(name, fields, "AsBytes");
+
+    Ok(quote::quote! {
+        #fields_are_no_uninit
+        #no_padding
+
+        unsafe impl ::memutils::NoUninit for #name {}
+    })
+}
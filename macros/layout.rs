@@ -0,0 +1,56 @@
+//! `#[derive(MemLayout)]`.
+
+use alloc::string::{String, ToString};
+use proc_macro2::TokenStream;
+
+use crate::common;
+
+/// Renders a type the way it is usually written, without the spaces `quote` puts between tokens.
+fn type_name(ty: &syn::Type) -> String {
+    let mut name = quote::quote!(#ty).to_string();
+    for (from, to) in [(" :: ", "::"), (":: ", "::"), (" ;", ";"), (" ,", ","), ("< ", "<"), (" <", "<"), (" >", ">"), ("& ", "&"), ("[ ", "["), (" ]", "]"), ("( ", "("), (" )", ")")] {
+        name = name.replace(from, to);
+    }
+    name
+}
+
+pub(crate) fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = common::struct_fields(&input, "MemLayout")?;
+    let name = &input.ident;
+
+    let entries = fields.iter().enumerate().map(|(i, field)| {
+        let ty = &field.ty;
+        let (member, label) = match &field.ident {
+            Some(ident) => (quote::quote!(#ident), ident.to_string()),
+            None => {
+                let index = syn::Index::from(i);
+                (quote::quote!(#index), i.to_string())
+            }
+        };
+        let ty_name = type_name(ty);
+        quote::quote! {
+            ::memutils::FieldLayout {
+                name: #label,
+                ty: #ty_name,
+                offset: ::core::mem::offset_of!(#name, #member),
+                size: ::core::mem::size_of::<#ty>(),
+                align: ::core::mem::align_of::<#ty>(),
+            }
+        }
+    });
+    let holes = fields.len() + 1;
+
+    Ok(quote::quote! {
+        impl ::memutils::MemLayout for #name {
+            const FIELDS: &'static [::memutils::FieldLayout] = &[#(#entries),*];
+            const PADDING: &'static [::memutils::PaddingHole] = {
+                const HOLES: ([::memutils::PaddingHole; #holes], usize) = ::memutils::padding_holes(
+                    <#name as ::memutils::MemLayout>::FIELDS,
+                    ::core::mem::size_of::<#name>(),
+                );
+                const ALL: &[::memutils::PaddingHole] = &HOLES.0;
+                ALL.split_at(HOLES.1).0
+            };
+        }
+    })
+}
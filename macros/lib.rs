@@ -1,4 +1,4 @@
-//! This crate contains the `#[memutils::nonsafe]` macro.
+//! This crate contains the `#[memutils::nonsafe]` macro and the memutils derives.
 
 #![no_std]
 
@@ -19,13 +19,21 @@
 #[allow(unused_extern_crates)]
 extern crate proc_macro;
 #[doc(hidden)]
+extern crate alloc;
+#[doc(hidden)]
 use proc_macro::TokenStream;
 
+mod common;
+mod from_bytes;
+mod layout;
+mod pod;
+mod reveal;
+
 /// Allows the creation of an unsafe function that is not marked as unsafe.
 /// Bypasses the `unsafe_code` lint.
 /// 
 /// ## Example
-/// ```rust
+/// ```rust,ignore
 /// #![deny(unsafe_code)]
 /// use memutils::*;
 /// 
@@ -88,3 +96,131 @@ pub fn not_safe(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Implements `Pod`, `Zeroable`, `FromBytes` and `NoUninit` for a struct.
+///
+/// Fails to compile unless the struct is `#[repr(C)]` or `#[repr(transparent)]`,
+/// every field is `Pod`, the struct has no padding and does not implement `Drop`.
+///
+/// ## Example
+/// ```rust,ignore
+/// use memutils::*;
+///
+/// #[derive(Pod)]
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     length: u32,
+/// }
+/// ```
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    pod::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromBytes` and `Zeroable` for a struct.
+///
+/// Fails to compile unless the struct is `#[repr(C)]` or `#[repr(transparent)]`
+/// and every field is `FromBytes`. Padding is allowed.
+///
+/// ## Example
+/// ```rust,ignore
+/// use memutils::*;
+///
+/// #[derive(FromBytes)]
+/// #[repr(C)]
+/// struct Entry {
+///     kind: u8,
+///     address: u64,
+/// }
+/// ```
+#[proc_macro_derive(FromBytes)]
+pub fn derive_from_bytes(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    from_bytes::derive_from_bytes(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `NoUninit` for a struct, which makes it `AsBytes`.
+///
+/// Fails to compile unless the struct is `#[repr(C)]` or `#[repr(transparent)]`,
+/// every field is `NoUninit` and the struct has no padding.
+///
+/// ## Example
+/// ```rust,ignore
+/// use memutils::*;
+///
+/// #[derive(AsBytes)]
+/// #[repr(C)]
+/// struct Flags {
+///     enabled: bool,
+///     level: u8,
+/// }
+/// ```
+#[proc_macro_derive(AsBytes)]
+pub fn derive_as_bytes(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    from_bytes::derive_as_bytes(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `MemLayout` for a struct, describing the name, type, offset,
+/// size and alignment of every field, and the padding holes between them.
+///
+/// Works with any `repr`, and with tuple structs, whose fields are named by index.
+///
+/// ## Example
+/// ```rust,ignore
+/// use memutils::*;
+///
+/// #[derive(MemLayout)]
+/// #[repr(C)]
+/// struct Entry {
+///     kind: u8,
+///     address: u64,
+/// }
+///
+/// assert_eq!(Entry::FIELDS[1].offset, 8);
+/// assert_eq!(Entry::PADDING, &[PaddingHole { offset: 1, size: 7 }]);
+/// ```
+#[proc_macro_derive(MemLayout)]
+pub fn derive_mem_layout(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    layout::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Reveal` for a struct, declaring a `{Name}Revealed` twin with
+/// the same fields, all public, and the same `repr`.
+///
+/// Fails to compile unless the struct is `#[repr(C)]` or `#[repr(transparent)]`,
+/// so that the two layouts are guaranteed to match.
+/// The twin implements `Debug` whenever all of its field types do.
+///
+/// ## Example
+/// ```rust,ignore
+/// use memutils::*;
+///
+/// #[derive(Reveal)]
+/// #[repr(C)]
+/// pub struct Counter {
+///     count: u32,
+/// }
+///
+/// let mut counter = Counter { count: 0 };
+/// counter.reveal_mut().count += 1;
+/// assert_eq!(counter.reveal().count, 1);
+/// ```
+#[proc_macro_derive(Reveal)]
+pub fn derive_reveal(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    reveal::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,27 @@
+//! `#[derive(Pod)]`.
+
+use alloc::format;
+use proc_macro2::TokenStream;
+
+use crate::common;
+
+pub(crate) fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = common::struct_fields(&input, "Pod")?;
+    common::require_stable_layout(&input, "Pod")?;
+
+    let name = &input.ident;
+    let fields_are_pod = common::assert_fields_impl(fields, &quote::quote!(::memutils::Pod));
+    let no_padding = common::assert_no_padding(name, fields, "Pod");
+    let message = format!("#[derive(Pod)]: `{}` must not implement Drop", name);
+
+    Ok(quote::quote! {
+        #fields_are_pod
+        #no_padding
+        const _: () = assert!(!::core::mem::needs_drop::<#name>(), #message);
+
+        unsafe impl ::memutils::Zeroable for #name {}
+        unsafe impl ::memutils::FromBytes for #name {}
+        unsafe impl ::memutils::NoUninit for #name {}
+        unsafe impl ::memutils::Pod for #name {}
+    })
+}
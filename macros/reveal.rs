@@ -0,0 +1,104 @@
+//! `#[derive(Reveal)]`.
+
+use alloc::format;
+use proc_macro2::TokenStream;
+
+use crate::common;
+
+pub(crate) fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[derive(Reveal)] only supports structs",
+            ))
+        }
+    };
+    common::require_stable_layout(&input, "Reveal")?;
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let revealed = quote::format_ident!("{}Revealed", name);
+    let reprs = input.attrs.iter().filter(|a| a.path().is_ident("repr"));
+    let doc = format!("The fields of [`{}`], made public by `#[derive(Reveal)]`.", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let params = &input.generics.params;
+
+    let twin_fields = fields.iter().map(|field| {
+        let docs = field.attrs.iter().filter(|a| a.path().is_ident("doc"));
+        let ty = &field.ty;
+        match &field.ident {
+            Some(ident) => quote::quote!(#(#docs)* pub #ident: #ty),
+            None => quote::quote!(#(#docs)* pub #ty),
+        }
+    });
+    let body = match fields {
+        syn::Fields::Named(_) => quote::quote!(#where_clause { #(#twin_fields,)* }),
+        syn::Fields::Unnamed(_) => quote::quote!((#(#twin_fields,)*) #where_clause;),
+        syn::Fields::Unit => quote::quote!(#where_clause;),
+    };
+
+    let members = fields.iter().enumerate().map(|(i, field)| match &field.ident {
+        Some(ident) => quote::quote!(#ident),
+        None => {
+            let index = syn::Index::from(i);
+            quote::quote!(#index)
+        }
+    });
+    let message = format!("#[derive(Reveal)]: `{}` and `{}` have different layouts", name, revealed);
+    let asserts = quote::quote! {
+        #(assert!(
+            ::core::mem::offset_of!(#name #ty_generics, #members) == ::core::mem::offset_of!(#revealed #ty_generics, #members),
+            #message
+        );)*
+    };
+    // Generic structs can only be checked once the parameters are known,
+    // so the asserts go into `Reveal::LAYOUT_CHECK`, which every method evaluates.
+    let (offsets_match, layout_check) = if input.generics.params.is_empty() {
+        (quote::quote!(const _: () = { #asserts };), TokenStream::new())
+    } else {
+        (TokenStream::new(), quote::quote!(const LAYOUT_CHECK: () = { #asserts };))
+    };
+
+    // The bounds are higher-ranked so that the impl is still accepted, and
+    // simply never applies, when a concrete field type is not `Debug`.
+    let field_types = fields.iter().map(|field| &field.ty);
+    let predicates = where_clause.into_iter().flat_map(|clause| clause.predicates.iter());
+    let label = format!("{}", revealed);
+    let debug_body = match fields {
+        syn::Fields::Named(_) => {
+            let names = fields.iter().map(|field| format!("{}", field.ident.as_ref().unwrap()));
+            let members = fields.iter().map(|field| &field.ident);
+            quote::quote!(f.debug_struct(#label)#(.field(#names, &self.#members))*.finish())
+        }
+        syn::Fields::Unnamed(_) => {
+            let members = (0..fields.len()).map(syn::Index::from);
+            quote::quote!(f.debug_tuple(#label)#(.field(&self.#members))*.finish())
+        }
+        syn::Fields::Unit => quote::quote!(f.write_str(#label)),
+    };
+
+    Ok(quote::quote! {
+        #[doc = #doc]
+        #(#reprs)*
+        #vis struct #revealed<#params> #body
+
+        #offsets_match
+
+        impl #impl_generics ::core::fmt::Debug for #revealed #ty_generics
+        where
+            #(#predicates,)*
+            #(for<'__reveal> #field_types: ::core::fmt::Debug,)*
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #debug_body
+            }
+        }
+
+        unsafe impl #impl_generics ::memutils::Reveal for #name #ty_generics #where_clause {
+            type Revealed = #revealed #ty_generics;
+            #layout_check
+        }
+    })
+}
@@ -44,7 +44,12 @@ pub(crate) mod mem;
 pub use mem::*;
 
 pub use memutilsmacros::{
-    not_safe
+    not_safe,
+    Pod,
+    FromBytes,
+    AsBytes,
+    MemLayout,
+    Reveal
 };
 pub use memutilscore::*;
 
@@ -1,11 +1,27 @@
 /// Allows the cloning of an object that does not implement `Clone`
+///
+/// For plain old data (see [`Pod`](crate::Pod)) this is safe: `clone!(&c)`
+/// copies the bytes of `c` without any allocation.
+///
+/// Any other type has to opt in explicitly with `clone!(unsafe &c)`,
+/// which goes through [`ByteClone`](crate::prelude::ByteClone).
+/// The intermediate copy is made with the global allocator, unless an
+/// allocator is passed as a second argument: `clone!(unsafe &c, alloc)`.
+///
+/// Adding `try` after `unsafe` returns a `Result` rather than aborting
+/// when the intermediate copy cannot be allocated: `clone!(unsafe try &c)`.
+/// To only inspect a copy of a type that owns memory, use
+/// [`ByteClone::shallow_clone`](crate::prelude::ByteClone::shallow_clone)
+/// instead, which never drops the copy.
 /// ## Safety
-/// This function is unsafe because it can cause a memory leak
-/// if the returned value is not freed.
+/// The `unsafe` forms copy the object bit for bit, so the clone
+/// shares any memory the original owns. Dropping both is a double free.
 /// ## Example
 /// ```rust
 /// use memutils::*;
 /// 
+/// #[derive(Debug, Pod)]
+/// #[repr(C)]
 /// struct Class {
 ///     data: u32,
 /// }
@@ -21,11 +37,119 @@
 /// ```
 #[macro_export]
 macro_rules! clone {
-    ($i:expr) => {
+    (unsafe try $i:expr) => {
+        {
+            use $crate::prelude::*;
+            ($i).try_byte_clone()
+        }
+    };
+    (unsafe try $i:expr, $alloc:expr) => {
+        {
+            use $crate::prelude::*;
+            ($i).try_byte_clone_in($alloc)
+        }
+    };
+    (unsafe $i:expr) => {
         {
-            use memutils::prelude::*;
+            use $crate::prelude::*;
             ($i).byte_clone()
         }
     };
+    (unsafe $i:expr, $alloc:expr) => {
+        {
+            use $crate::prelude::*;
+            ($i).byte_clone_in($alloc)
+        }
+    };
+    ($i:expr) => {
+        $crate::byte_clone($i)
+    };
     () => ();
 }
+
+/// Returns the offset of a field from the start of its struct, in bytes.
+///
+/// Nested fields are written as a path: `offset_of!(Outer, inner.field)`.
+/// ## Example
+/// ```rust
+/// use memutils::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     length: u16,
+/// }
+///
+/// assert_eq!(offset_of!(Header, length), 4);
+/// ```
+#[macro_export]
+macro_rules! offset_of {
+    ($t:ty, $($field:tt)+) => {
+        ::core::mem::offset_of!($t, $($field)+)
+    };
+}
+
+/// Turns a raw pointer to a field into a raw pointer to the struct that contains it.
+///
+/// Takes a `*const` or `*mut` pointer and returns the same kind of pointer.
+/// Building the pointer is safe; dereferencing it is only sound if the field
+/// really is the named field of a live value of that struct.
+/// ## Example
+/// ```rust
+/// use memutils::*;
+///
+/// #[repr(C)]
+/// struct Node {
+///     value: u32,
+///     link: usize,
+/// }
+///
+/// let node = Node { value: 7, link: 0 };
+/// let link: *const usize = &node.link;
+/// let parent = container_of!(link, Node, link);
+/// assert_eq!(unsafe { (*parent).value }, 7);
+/// ```
+#[macro_export]
+macro_rules! container_of {
+    ($ptr:expr, $t:ty, $($field:tt)+) => {
+        ($ptr).wrapping_byte_sub(::core::mem::offset_of!($t, $($field)+)).cast::<$t>()
+    };
+}
+
+/// Declares an [`Adapter`](crate::Adapter) that finds the [`ListLink`](crate::ListLink)
+/// field of a struct, so the struct can be put in an [`IntrusiveList`](crate::IntrusiveList).
+/// ## Example
+/// ```rust
+/// use core::pin::Pin;
+/// use memutils::*;
+///
+/// struct Task {
+///     id: u32,
+///     link: ListLink,
+/// }
+///
+/// intrusive_adapter!(TaskAdapter = Task { link });
+///
+/// static FIRST: Task = Task { id: 1, link: ListLink::new() };
+/// static SECOND: Task = Task { id: 2, link: ListLink::new() };
+///
+/// let mut list = IntrusiveList::<Task, TaskAdapter>::new();
+/// list.push_back(Pin::static_ref(&FIRST));
+/// list.push_back(Pin::static_ref(&SECOND));
+/// assert_eq!(list.iter().map(|task| task.id).collect::<Vec<_>>(), [1, 2]);
+/// ```
+#[macro_export]
+macro_rules! intrusive_adapter {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $t:ty { $($field:tt)+ }) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        $vis struct $name;
+
+        unsafe impl $crate::Adapter<$t> for $name {
+            const OFFSET: usize = {
+                let _: fn(&$t) -> &$crate::ListLink = |node| &node.$($field)+;
+                ::core::mem::offset_of!($t, $($field)+)
+            };
+        }
+    };
+}
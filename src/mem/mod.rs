@@ -1,7 +1,88 @@
 /// Allows you to expose private fields of a struct
+///
+/// Revealing an object declares a `#[repr(C)]` mirror struct with the given
+/// fields and reinterprets the object as it. Casting reinterprets the object
+/// as an existing type. Either way, the build fails unless the object and the
+/// target have the same size, so a typo in the mirror cannot silently read
+/// garbage. Borrowing forms also require the same alignment.
+///
+/// - `mem!(obj as { a: u32, b: u16, })` moves `obj` into a mirror struct.
+/// - `mem!(obj as (u32, u16))` uses a tuple mirror struct instead.
+/// - `mem!(&obj as { ... })` and `mem!(&mut obj as { ... })` borrow `obj`
+///   instead of moving it, and return a reference to the mirror.
+/// - `mem!(obj as <T> { value: T, })` declares a generic mirror, for
+///   objects whose type is generic over `T`.
+/// - `mem!(obj as Type)`, `mem!(&obj as Type)` and `mem!(&mut obj as Type)`
+///   cast to an existing type.
+///
+/// The `turn obj into ...` and `cast obj to Type` spellings work too, and
+/// `turn obj into { ... } named Name` names the mirror struct.
+/// ## Safety
+/// The mirror has a `repr(C)` layout, so the source should have one too;
+/// otherwise its fields may be in a different order even when the size and
+/// alignment match. The bytes of the object must be valid for the target.
+/// ## Example
+/// ```rust
+/// use memutils::*;
+///
+/// mod private {
+///     #[repr(C)]
+///     pub struct Secret {
+///         key: u32,
+///         id: u16,
+///     }
+///
+///     impl Secret {
+///         pub fn new() -> Self {
+///             Self { key: 7, id: 1 }
+///         }
+///     }
+/// }
+///
+/// let secret = private::Secret::new();
+/// let revealed = unsafe { mem!(&secret as { key: u32, id: u16 }) };
+/// assert_eq!(revealed.key, 7);
+/// ```
 #[macro_export]
 macro_rules! mem {
+    // Revealing by reference
+    (&mut $obj:ident as $(<$($g:ident),+ $(,)?>)? { $($fields:tt)* }) => {
+        $crate::mem_internals!(@MIRROR MUT &mut $obj; $obj $(<$($g),+>)? { $($fields)* })
+    };
+    (&mut $obj:ident as $(<$($g:ident),+ $(,)?>)? ( $($fields:tt)* )) => {
+        $crate::mem_internals!(@MIRROR_TUPLE MUT &mut $obj; __MemMirror $(<$($g),+>)? ( $($fields)* ))
+    };
+    (& $obj:ident as $(<$($g:ident),+ $(,)?>)? { $($fields:tt)* }) => {
+        $crate::mem_internals!(@MIRROR REF &$obj; $obj $(<$($g),+>)? { $($fields)* })
+    };
+    (& $obj:ident as $(<$($g:ident),+ $(,)?>)? ( $($fields:tt)* )) => {
+        $crate::mem_internals!(@MIRROR_TUPLE REF &$obj; __MemMirror $(<$($g),+>)? ( $($fields)* ))
+    };
+
+    // Revealing by value
+    ($obj:ident as $(<$($g:ident),+ $(,)?>)? { $($fields:tt)* }) => {
+        $crate::mem_internals!(@MIRROR VAL $obj; $obj $(<$($g),+>)? { $($fields)* })
+    };
+    ($obj:ident as $(<$($g:ident),+ $(,)?>)? ( $($fields:tt)* )) => {
+        $crate::mem_internals!(@MIRROR_TUPLE VAL $obj; __MemMirror $(<$($g),+>)? ( $($fields)* ))
+    };
+    (turn $obj:ident into $(<$($g:ident),+ $(,)?>)? { $($fields:tt)* } named $name:ident) => {
+        $crate::mem_internals!(@MIRROR VAL $obj; $name $(<$($g),+>)? { $($fields)* })
+    };
+    (turn $obj:ident into $(<$($g:ident),+ $(,)?>)? { $($fields:tt)* }) => {
+        $crate::mem_internals!(@MIRROR VAL $obj; $obj $(<$($g),+>)? { $($fields)* })
+    };
+    (turn $obj:ident into $(<$($g:ident),+ $(,)?>)? ( $($fields:tt)* )) => {
+        $crate::mem_internals!(@MIRROR_TUPLE VAL $obj; __MemMirror $(<$($g),+>)? ( $($fields)* ))
+    };
+
     // Casting
+    (&mut $obj:ident as $t:ty) => {
+        $crate::mem_internals!(@MUT &mut $obj;$t)
+    };
+    (& $obj:ident as $t:ty) => {
+        $crate::mem_internals!(@REF &$obj;$t)
+    };
     (turn $obj:ident into $t:ty) => {
         $crate::mem!(@CAST $obj;$t)
     };
@@ -16,29 +97,8 @@ macro_rules! mem {
     };
 
     // Transforrming
-    ($obj:ident as {
-        $($field_vis:vis $field_name:ident: $field_type:ty,)*
-    }) => {
-        $crate::mem!(@REVEAL $obj;$obj {
-            $($field_vis $field_name: $field_type,)*
-        })
-    };
-    (turn $obj:ident into {
-        $($field_vis:vis $field_name:ident: $field_type:ty,)*
-    }) => {
-        $crate::mem!(@REVEAL $obj;$obj {
-            $($field_vis $field_name: $field_type,)*
-        })
-    };
-    (turn $obj:ident into {
-        $($field_vis:vis $field_name:ident: $field_type:ty,)*
-    } named $name:ident) => {
-        $crate::mem!(@REVEAL $obj;$name {
-            $($field_vis $field_name: $field_type,)*
-        })
-    };
     (@REVEAL $obj:ident;$name:ident{
-        $($field_vis:vis $field_name:ident: $field_type:ty,)*
+        $($field_vis:vis $field_name:ident: $field_type:ty),* $(,)?
     }) => {
         $crate::mem_internals!(@FROM $obj;$name {
             $($field_vis $field_name: $field_type,)*
@@ -52,24 +112,51 @@ macro_rules! mem {
 /// Internals of the mem! macro
 #[macro_export(local_inner_macros)]
 macro_rules! mem_internals {
-    (@FROM $obj:expr;$name:ident {
-        $($field_vis:vis $field_name:ident: $field_type:ty,)*
+    (@MIRROR $mode:ident $src:expr; $name:ident $(<$($g:ident),+>)? {
+        $($field_vis:vis $field_name:ident: $field_type:ty),* $(,)?
     }) => {
-        {
-            #[allow(non_camel_case_types)]
-            #[repr(transparent)]
-            struct $name {
-                $($field_vis $field_name: $field_type,)*
+        match $src {
+            __mem_src => {
+                #[allow(non_camel_case_types, dead_code)]
+                #[repr(C)]
+                struct $name $(<$($g),+>)? {
+                    $($field_vis $field_name: $field_type,)*
+                }
+                $crate::mem_internals!(@$mode __mem_src;$name $(<$($g),+>)?)
             }
-            $crate::mem_internals!(@CAST $obj;$name)
         }
     };
-    (@CAST $obj:expr;$t:ty) => {
-        {
-            let res: $t = core::mem::transmute($obj);
-            res
+    (@MIRROR_TUPLE $mode:ident $src:expr; $name:ident $(<$($g:ident),+>)? (
+        $($field_vis:vis $field_type:ty),* $(,)?
+    )) => {
+        match $src {
+            __mem_src => {
+                #[allow(non_camel_case_types, dead_code)]
+                #[repr(C)]
+                struct $name $(<$($g),+>)? ($($field_vis $field_type,)*);
+                $crate::mem_internals!(@$mode __mem_src;$name $(<$($g),+>)?)
+            }
         }
     };
+    (@FROM $obj:expr;$name:ident {
+        $($field_vis:vis $field_name:ident: $field_type:ty,)*
+    }) => {
+        $crate::mem_internals!(@MIRROR VAL $obj;$name {
+            $($field_vis $field_name: $field_type,)*
+        })
+    };
+    (@VAL $obj:expr;$t:ty) => {
+        $crate::transmute_checked::<_, $t>($obj)
+    };
+    (@REF $obj:expr;$t:ty) => {
+        $crate::transmute_ref::<_, $t>($obj)
+    };
+    (@MUT $obj:expr;$t:ty) => {
+        $crate::transmute_mut::<_, $t>($obj)
+    };
+    (@CAST $obj:expr;$t:ty) => {
+        $crate::mem_internals!(@VAL $obj;$t)
+    };
 
     () => ();
 }
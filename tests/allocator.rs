@@ -0,0 +1,175 @@
+#![deny(unsafe_code)]
+
+#[cfg(test)]
+use core::cell::Cell;
+
+#[allow(unused)]
+use memutils::*;
+
+/// Forwards to the global allocator, counting live blocks.
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct Counting {
+    live: Cell<isize>,
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+unsafe impl RawAllocator for Counting {
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        self.live.set(self.live.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn dynarray_uses_custom_allocator() {
+    let alloc = Counting::default();
+    {
+        let mut arr = DynArray::new_in(&alloc);
+        for i in 0..64u64 {
+            arr.push(i);
+        }
+        assert_eq!(alloc.live.get(), 1);
+        assert_eq!(arr.iter().sum::<u64>(), 2016);
+    }
+    assert_eq!(alloc.live.get(), 0);
+}
+
+#[test]
+#[not_safe]
+fn clone_uses_custom_allocator() {
+    struct Object {
+        data: u64,
+    }
+
+    let alloc = Counting::default();
+    let c = Object { data: 42 };
+    let c2 = clone!(unsafe &c, &alloc);
+    assert_eq!(c2.data, 42);
+    assert_eq!(alloc.live.get(), 0);
+
+    let guard = create_object_byteguard_from_pointer_in(&c as *const Object, &alloc);
+    assert_eq!(alloc.live.get(), 1);
+    drop(guard);
+    assert_eq!(alloc.live.get(), 0);
+}
+
+/// Refuses every allocation.
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct Exhausted;
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+unsafe impl RawAllocator for Exhausted {
+    fn allocate(&self, _: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn deallocate(&self, _: *mut u8, _: Layout) {}
+}
+
+#[test]
+#[not_safe]
+fn allocation_failure_is_reported() {
+    struct Object {
+        data: u64,
+    }
+
+    let c = Object { data: 42 };
+    let err = clone!(unsafe try &c, Exhausted).err();
+    assert_eq!(err, Some(AllocError::OutOfMemory(Layout::new::<Object>())));
+    assert_eq!(clone!(unsafe try &c).map(|o| o.data), Ok(42));
+
+    let mut arr = DynArray::new_in(Exhausted);
+    assert!(arr.try_push(1u8).is_err());
+    assert!(arr.is_empty());
+    assert_eq!(DynArray::<u64>::try_with_capacity(usize::MAX).err(), Some(AllocError::CapacityOverflow));
+}
+
+#[test]
+#[not_safe]
+fn fallible_copies_of_zero_sized_values() {
+    #[derive(Debug, PartialEq)]
+    struct Marker;
+
+    assert_eq!(clone!(unsafe try &Marker, Exhausted), Ok(Marker));
+    let mut marker = Marker;
+    assert_eq!(<Marker as memutils::prelude::FromRawPointer<Marker>>::try_from_raw_pointer_in(&mut marker, Exhausted), Ok(Marker));
+}
+
+/// Forwards to the global allocator, refusing the first shrink
+/// and counting live bytes.
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct StubbornShrink {
+    refused: Cell<bool>,
+    live: Cell<usize>,
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+unsafe impl RawAllocator for StubbornShrink {
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        self.live.set(self.live.get() + layout.size());
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        self.live.set(self.live.get().checked_sub(layout.size()).expect("freed more bytes than allocated"));
+        Global.deallocate(ptr, layout)
+    }
+
+    unsafe fn shrink(&self, ptr: *mut u8, old_layout: Layout, new_layout: Layout) -> *mut u8 {
+        if !self.refused.replace(true) {
+            return core::ptr::null_mut();
+        }
+        let new = self.allocate(new_layout);
+        core::ptr::copy_nonoverlapping(ptr, new, new_layout.size());
+        self.deallocate(ptr, old_layout);
+        new
+    }
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn failed_shrink_leaves_the_guard_unchanged() {
+    let alloc = StubbornShrink::default();
+    let mut guard = ByteGuard::from_slice_in(&[1u32, 2, 3, 4], &alloc);
+    assert_eq!(guard.try_resize(2, 0), Err(AllocError::OutOfMemory(Layout::new::<[u32; 2]>())));
+    assert_eq!(&*guard, &[1, 2, 3, 4]);
+    assert_eq!(guard.layout().size(), 16);
+
+    assert_eq!(guard.try_resize(2, 0), Ok(()));
+    assert_eq!(&*guard, &[1, 2]);
+    assert_eq!(guard.layout().size(), 8);
+
+    let (ptr, alloc) = guard.into_raw_with_allocator();
+    let guard = unsafe { ByteGuard::from_raw_in(ptr, alloc) };
+    assert_eq!(alloc.live.get(), 8);
+    drop(guard);
+    assert_eq!(alloc.live.get(), 0);
+}
+
+#[test]
+fn shrinking_drops_the_truncated_values() {
+    use std::rc::Rc;
+
+    let shared = Rc::new(());
+    let alloc = StubbornShrink::default();
+    let mut guard = ByteGuard::from_slice_in(&[Rc::clone(&shared), Rc::clone(&shared), Rc::clone(&shared)], &alloc);
+    assert_eq!(Rc::strong_count(&shared), 4);
+    assert_eq!(guard.try_resize(1, Rc::clone(&shared)), Ok(()));
+    assert_eq!(guard.len(), 1);
+    assert_eq!(alloc.live.get(), 8);
+    assert_eq!(Rc::strong_count(&shared), 2);
+    drop(guard);
+    assert_eq!(Rc::strong_count(&shared), 1);
+    assert_eq!(alloc.live.get(), 0);
+}
@@ -0,0 +1,90 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+fn allocations_from_chunks() {
+    let arena = Arena::new().with_chunk_size(64);
+    let number = arena.alloc(5u64);
+    let text = arena.alloc_str("hello");
+    let values = arena.alloc_slice_copy(&[1u16, 2, 3]);
+    *number += 1;
+    text.make_ascii_uppercase();
+    values[0] = 10;
+    assert_eq!(*number, 6);
+    assert_eq!(text, "HELLO");
+    assert_eq!(values, &[10, 2, 3]);
+    assert!((number as *mut u64).is_aligned());
+
+    let big = arena.alloc([7u8; 200]);
+    assert_eq!(big[199], 7);
+    assert_eq!(arena.alloc(()), &());
+    assert!(format!("{arena:?}").contains("chunks: 2"));
+}
+
+#[test]
+fn buffers_do_not_grow() {
+    let mut buffer = [0u8; 32];
+    let arena = Arena::from_buffer(&mut buffer);
+    assert_eq!(arena.remaining(), 32);
+    arena.alloc_slice_copy(&[1u8; 20]);
+    assert_eq!(arena.try_alloc([0u8; 16]), Err(AllocError::OutOfMemory(Layout::new::<[u8; 16]>())));
+    assert_eq!(arena.alloc(9u8), &9);
+}
+
+#[test]
+fn buffers_spill_into_chunks() {
+    let mut buffer = [0u8; 8];
+    let arena = Arena::from_buffer_in(&mut buffer, Global);
+    assert_eq!(arena.alloc(1u64), &1);
+    assert_eq!(arena.alloc(2u64), &2);
+    assert!(format!("{arena:?}").contains("chunks: 1"));
+}
+
+#[test]
+fn checkpoints_and_reset() {
+    let mut buffer = [0u8; 16];
+    let mut arena = Arena::from_buffer_in(&mut buffer, Global).with_chunk_size(32);
+    arena.alloc(1u32);
+    let checkpoint = arena.checkpoint();
+    let remaining = arena.remaining();
+    for i in 0..20u32 {
+        arena.alloc(i);
+    }
+    assert!(!format!("{arena:?}").contains("chunks: 0"));
+
+    arena.rewind(checkpoint);
+    assert_eq!(arena.remaining(), remaining);
+    assert!(format!("{arena:?}").contains("chunks: 0"));
+
+    arena.alloc([0u8; 64]);
+    arena.reset();
+    assert_eq!(arena.remaining(), 16);
+}
+
+#[test]
+#[should_panic(expected = "does not belong")]
+fn foreign_checkpoints_panic() {
+    let other = Arena::new();
+    other.alloc(1u8);
+    let checkpoint = other.checkpoint();
+    let mut arena = Arena::new();
+    arena.rewind(checkpoint);
+}
+
+#[test]
+fn collections_live_in_arenas() {
+    let arena = Arena::new().with_chunk_size(128);
+    let guard = ByteGuard::new_in([1u32, 2, 3], &arena);
+    assert_eq!(*guard, [1, 2, 3]);
+
+    let mut array = DynArray::new_in(&arena);
+    for i in 0..100u32 {
+        array.push(i);
+    }
+    assert_eq!(array.iter().sum::<u32>(), 4950);
+    let (remaining, spare) = (arena.remaining(), array.capacity() - array.len());
+    array.shrink_to_fit();
+    assert_eq!(arena.remaining(), remaining + spare * 4);
+}
@@ -0,0 +1,111 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+use core::alloc::GlobalAlloc;
+
+#[cfg(test)]
+#[repr(align(4096))]
+struct Region([u8; 4096]);
+
+#[cfg(test)]
+fn buddy(min_order: u32, max_order: u32) -> BuddyAllocator<'static> {
+    let region = Box::leak(Box::new(Region([0; 4096])));
+    let bitmap = vec![0; BuddyAllocator::bitmap_words(4096, min_order, max_order)].leak();
+    BuddyAllocator::from_slice(&mut region.0, min_order, max_order, bitmap)
+}
+
+#[test]
+fn split_and_merge() {
+    let mut buddy = buddy(4, 12);
+    assert_eq!(buddy.free_bytes(), 4096);
+    assert_eq!(buddy.free_blocks(12), 1);
+
+    let small = buddy.allocate(Layout::new::<u8>()).unwrap();
+    assert!(small.as_ptr().addr().is_multiple_of(16));
+    assert_eq!(buddy.free_blocks(12), 0);
+    for order in 4..12 {
+        assert_eq!(buddy.free_blocks(order), 1, "order {order}");
+    }
+    let page = buddy.allocate(Layout::from_size_align(1024, 1024).unwrap()).unwrap();
+    assert!(page.as_ptr().addr().is_multiple_of(1024));
+    assert_eq!(buddy.free_bytes(), 4096 - 16 - 1024);
+
+    #[allow(unsafe_code)]
+    unsafe {
+        buddy.deallocate(small, Layout::new::<u8>());
+        buddy.deallocate(page, Layout::from_size_align(1024, 1024).unwrap());
+    }
+    assert_eq!(buddy.free_blocks(12), 1);
+    assert_eq!(buddy.free_bytes(), 4096);
+}
+
+#[test]
+fn orders_are_configurable() {
+    let mut buddy = buddy(6, 10);
+    assert_eq!(buddy.free_blocks(10), 4);
+    assert_eq!(buddy.order_for(Layout::new::<u8>()), Some(6));
+    assert_eq!(buddy.order_for(Layout::from_size_align(2048, 8).unwrap()), None);
+    let layout = Layout::from_size_align(2048, 8).unwrap();
+    assert_eq!(buddy.allocate(layout), Err(AllocError::OutOfMemory(layout)));
+
+    let blocks: Vec<_> = (0..64).map(|_| buddy.allocate_block(6).unwrap()).collect();
+    assert_eq!(buddy.allocate_block(6), None);
+    assert_eq!(buddy.free_bytes(), 0);
+    for block in blocks.into_iter().rev() {
+        #[allow(unsafe_code)]
+        unsafe { buddy.deallocate_block(block, 6) };
+    }
+    assert_eq!(buddy.free_blocks(10), 4);
+}
+
+#[test]
+fn unaligned_regions_use_smaller_blocks() {
+    let memory = vec![0u8; 1000].leak();
+    let bitmap = vec![0; BuddyAllocator::bitmap_words(1000, 4, 8)].leak();
+    let buddy = BuddyAllocator::from_slice(memory, 4, 8, bitmap);
+    assert!(buddy.size() > 900 && buddy.size() <= 1000);
+    assert_eq!(buddy.free_bytes(), buddy.size());
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn frames_outside_memory() {
+    let bitmap = vec![0; BuddyAllocator::bitmap_words(1 << 20, 12, 16)].leak();
+    let mut frames = unsafe { BuddyAllocator::from_addr(0x10_0000, 1 << 20, 12, 16, bitmap) };
+    let first = frames.allocate_block(12).unwrap();
+    let second = frames.allocate_block(12).unwrap();
+    assert_eq!(first, 0x10_0000);
+    assert_eq!(second, 0x10_1000);
+    let large = frames.allocate_block(16).unwrap();
+    assert!(large.is_multiple_of(1 << 16));
+    unsafe { frames.deallocate_block(second, 12) };
+    assert_eq!(frames.allocate_block(12), Some(second));
+}
+
+#[test]
+#[should_panic(expected = "bitmap needs")]
+fn small_bitmaps_panic() {
+    let _ = BuddyAllocator::from_slice(vec![0u8; 4096].leak(), 4, 12, &mut [0; 2]);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn locked_buddy_allocator() {
+    static BUDDY: LockedBuddyAllocator<'static> = LockedBuddyAllocator::empty();
+    let layout = Layout::new::<u64>();
+    assert!(unsafe { BUDDY.alloc(layout) }.is_null());
+
+    BUDDY.init(buddy(4, 12));
+    let ptr = unsafe { BUDDY.alloc(layout) };
+    assert!(!ptr.is_null());
+    unsafe { BUDDY.dealloc(ptr, layout) };
+
+    let mut array = DynArray::new_in(&BUDDY);
+    array.extend(0..64u32);
+    assert_eq!(array.iter().sum::<u32>(), 2016);
+    drop(array);
+    assert_eq!(BUDDY.lock().as_ref().unwrap().free_bytes(), 4096);
+}
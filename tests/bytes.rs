@@ -6,6 +6,7 @@ use memutils::*;
 #[test]
 #[not_safe]
 fn no_heap_corrupt_test() {
+    #[derive(Pod)]
     #[repr(transparent)]
     struct Object {
         data: u32,
@@ -19,3 +20,166 @@ fn no_heap_corrupt_test() {
     assert!(c2.data == 10)
 
 }
+
+#[test]
+fn pod_clone_is_safe() {
+    #[derive(Pod)]
+    #[repr(C)]
+    struct Inner {
+        a: u16,
+        b: [u8; 2],
+    }
+
+    #[derive(Pod)]
+    #[repr(C)]
+    struct Outer {
+        inner: Inner,
+        c: u32,
+    }
+
+    let o = Outer { inner: Inner { a: 1, b: [2, 3] }, c: 4 };
+    let o2 = clone!(&o);
+    assert_eq!((o2.inner.a, o2.inner.b, o2.c), (1, [2, 3], 4));
+
+    let z = Outer::zeroed();
+    assert_eq!((z.inner.a, z.inner.b, z.c), (0, [0, 0], 0));
+}
+
+#[test]
+#[not_safe]
+fn non_pod_clone_is_opt_in() {
+    struct Object {
+        data: Option<&'static str>,
+    }
+
+    let c = Object { data: Some("memutils") };
+    let c2 = clone!(unsafe &c);
+    assert_eq!(c2.data, Some("memutils"));
+}
+
+#[test]
+fn shallow_copy_is_never_dropped() {
+    use memutils::prelude::ByteClone;
+    use std::rc::Rc;
+
+    struct Foreign {
+        name: String,
+        shared: Rc<u8>,
+    }
+
+    let original = Foreign { name: String::from("foreign"), shared: Rc::new(1) };
+    {
+        let copy = original.shallow_clone();
+        assert_eq!(copy.name, "foreign");
+        assert_eq!(copy.bytes().len(), core::mem::size_of::<Foreign>());
+    }
+
+    assert_eq!(Rc::strong_count(&original.shared), 1);
+    assert_eq!(original.name, "foreign");
+}
+
+#[test]
+fn byteguard_clone_is_deep() {
+    use std::rc::Rc;
+
+    let shared = Rc::new(5u32);
+    let guard = ByteGuard::new(Rc::clone(&shared));
+    let copy = guard.clone();
+    assert_eq!(Rc::strong_count(&shared), 3);
+    assert_ne!(guard.as_ptr(), copy.as_ptr());
+
+    drop(guard);
+    drop(copy);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn byteguard_slices() {
+    let mut guard = ByteGuard::from_slice(&[1u16, 2, 3]);
+    assert_eq!(&*guard, &[1, 2, 3]);
+
+    guard.resize(6, 9);
+    assert_eq!(&*guard, &[1, 2, 3, 9, 9, 9]);
+    guard.resize(2, 0);
+    assert_eq!(&*guard, &[1, 2]);
+    assert_eq!(guard.layout().size(), 4);
+
+    let zeroed = ByteGuard::<[u64]>::new_zeroed_slice(4);
+    #[allow(unsafe_code)]
+    let zeroed = unsafe { zeroed.assume_init() };
+    assert_eq!(&*zeroed, &[0; 4]);
+}
+
+#[test]
+fn byteguard_raw_round_trip() {
+    let mut guard = ByteGuard::new_uninit().write(String::from("memutils"));
+    guard.push('!');
+    let raw = guard.into_raw();
+    #[allow(unsafe_code)]
+    let guard = unsafe { ByteGuard::from_raw(raw) };
+    assert_eq!(guard.into_inner(), "memutils!");
+}
+
+#[test]
+fn unsized_byte_objects() {
+    let values = [1u16, 2, 3];
+    let slice = ByteObject::from(&values[..]);
+    assert_eq!(slice.len(), 6);
+    assert_eq!(slice.metadata(), 3);
+    assert_eq!(slice.bytes(), values.as_bytes());
+
+    let text = ByteObject::from("hello");
+    assert_eq!(text.bytes(), b"hello");
+    assert_eq!(text.metadata(), 5);
+
+    let value = 7u64;
+    let object: &dyn core::fmt::Debug = &value;
+    let object = ByteObject::from(object);
+    assert_eq!(object.len(), 8);
+    assert!(format!("{:?}", object.metadata()).contains("DynMetadata"));
+    assert_eq!(object.bytes(), &7u64.to_ne_bytes());
+
+    let null = Null::<[u8]>::with_metadata(4);
+    assert_eq!(null.metadata(), 4);
+    assert!(null.as_ptr().is_null());
+    assert_ne!(null, Null::with_metadata(5));
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn checked_go_and_gom() {
+    let mut values = [1u32, 2];
+    assert_eq!(unsafe { try_go(values.as_ptr()) }, Ok(&1));
+    *unsafe { try_gom(values.as_mut_ptr()) }.unwrap() = 3;
+    assert_eq!(values[0], 3);
+
+    assert_eq!(unsafe { try_go(core::ptr::null::<u32>()) }, Err(PointerError::Null));
+    let misaligned = values.as_ptr().cast::<u8>().wrapping_add(1).cast::<u32>();
+    assert!(matches!(check_pointer(misaligned), Err(PointerError::Misaligned { align: 4, .. })));
+
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    {
+        let unmapped = core::ptr::without_provenance::<u64>(0x1000);
+        assert_eq!(check_pointer(unmapped), Err(PointerError::Unmapped { addr: 0x1000, len: 8 }));
+
+        static TEXT: [u8; 4] = *b"text";
+        assert_eq!(check_pointer(&TEXT), Ok(()));
+        let ro = (&TEXT as *const [u8; 4]).cast_mut();
+        assert!(matches!(check_pointer_mut(ro), Err(PointerError::Unmapped { .. })));
+    }
+}
+
+#[test]
+#[should_panic(expected = "null pointer")]
+#[allow(unsafe_code)]
+#[cfg(debug_assertions)]
+fn gom_asserts_non_null_in_debug_builds() {
+    let _ = unsafe { gom(core::ptr::null_mut::<u32>()) };
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn go_in_const_context() {
+    const VALUE: u32 = unsafe { *go(&7u32) };
+    assert_eq!(VALUE, 7);
+}
@@ -0,0 +1,94 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+#[derive(Debug, Pod)]
+#[repr(C)]
+struct Header {
+    magic: u32,
+    length: u16,
+    kind: u16,
+}
+
+#[cfg(test)]
+#[repr(C, align(8))]
+struct Aligned([u8; 32]);
+
+#[test]
+fn ref_from_bytes_checks_size_and_alignment() {
+    let mut buf = Aligned([0; 32]);
+    buf.0[..8].copy_from_slice(&[0x78, 0x56, 0x34, 0x12, 8, 0, 1, 0]);
+
+    let header: &Header = ref_from_bytes(&buf.0[..8]).unwrap();
+    assert_eq!(header.magic, u32::from_le_bytes([0x78, 0x56, 0x34, 0x12]));
+    assert_eq!(header.length, u16::from_le_bytes([8, 0]));
+
+    assert_eq!(
+        ref_from_bytes::<Header>(&buf.0[..7]).unwrap_err(),
+        CastError::Size { expected: 8, actual: 7 }
+    );
+    assert!(matches!(
+        ref_from_bytes::<Header>(&buf.0[1..9]),
+        Err(CastError::Alignment { align: 4, .. })
+    ));
+}
+
+#[test]
+fn prefix_and_suffix() {
+    let mut buf = Aligned([0; 32]);
+
+    let (header, rest) = mut_from_prefix::<Header>(&mut buf.0).unwrap();
+    header.kind = 7;
+    assert_eq!(rest.len(), 24);
+    assert_eq!(ref_from_prefix::<Header>(&buf.0).unwrap().0.kind, 7);
+
+    let (rest, last) = ref_from_suffix::<u64>(&buf.0).unwrap();
+    assert_eq!(rest.len(), 24);
+    assert_eq!(*last, 0);
+
+    assert_eq!(
+        ref_from_prefix::<Header>(&buf.0[..4]).unwrap_err(),
+        CastError::Size { expected: 8, actual: 4 }
+    );
+}
+
+#[test]
+fn slices_and_bytes() {
+    let mut buf = Aligned([0; 32]);
+
+    let words = slice_from_bytes_mut::<u32>(&mut buf.0).unwrap();
+    assert_eq!(words.len(), 8);
+    words[1] = u32::MAX;
+    assert_eq!(&buf.0[4..8], &[0xff; 4]);
+
+    assert!(slice_from_bytes::<u32>(&buf.0[..6]).is_err());
+    assert_eq!(read_from_bytes::<u32>(&buf.0[3..]).unwrap(), u32::from_le_bytes([0, 0xff, 0xff, 0xff]));
+
+    let header = Header { magic: 1, length: 2, kind: 3 };
+    assert_eq!(header.as_bytes().len(), 8);
+    assert_eq!([1u16, 2].as_bytes(), &[1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat()[..]);
+}
+
+#[test]
+fn derives() {
+    #[derive(FromBytes)]
+    #[repr(C)]
+    struct Padded {
+        kind: u8,
+        value: u32,
+    }
+
+    #[derive(AsBytes)]
+    #[repr(C)]
+    struct Flags {
+        enabled: bool,
+        level: u8,
+    }
+
+    let buf = [0u32; 2];
+    let padded: Padded = read_from_bytes(buf.as_bytes()).unwrap();
+    assert_eq!((padded.kind, padded.value), (0, 0));
+    assert_eq!(Flags { enabled: true, level: 2 }.as_bytes(), &[1, 2]);
+}
@@ -0,0 +1,50 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod)]
+#[repr(C)]
+struct State {
+    id: u32,
+    flags: [u8; 4],
+    counter: u64,
+}
+
+#[test]
+fn diff_coalesces_ranges() {
+    let old = State { id: 1, flags: [0; 4], counter: 0 };
+    let new = State { id: 1, flags: [1, 1, 0, 1], counter: 0 };
+
+    let old_bytes = ByteObject::from(&old);
+    let new_bytes = ByteObject::from(&new);
+    let diff = old_bytes.diff(&new_bytes);
+    assert_eq!(diff.ranges(), &[4..6, 7..8]);
+    assert_eq!(diff.changed_bytes(), 3);
+    assert_eq!(
+        diff.to_string(),
+        "00000004  00 00                   | 01 01\n00000007  00                      | 01"
+    );
+    assert!(old_bytes.diff(&old_bytes).is_empty());
+}
+
+#[test]
+fn patch_applies_to_values_and_guards() {
+    let old = State { id: 1, flags: [0; 4], counter: 0 };
+    let new = State { id: 2, flags: [0; 4], counter: u64::MAX };
+    let patch = ByteObject::from(&old).diff(&ByteObject::from(&new)).to_patch();
+    assert_eq!(patch.len(), 16);
+
+    let mut target = old;
+    patch.apply(&mut target).unwrap();
+    assert_eq!(target, new);
+
+    let mut guard = ByteGuard::new(old);
+    patch.apply(&mut *guard).unwrap();
+    assert_eq!(*guard, new);
+
+    let mut short = [0u32; 2];
+    assert_eq!(patch.apply(&mut short).unwrap_err(), CastError::Size { expected: 16, actual: 8 });
+    assert_eq!(short, [0; 2]);
+}
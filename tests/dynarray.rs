@@ -0,0 +1,105 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+fn push_pop_insert_remove() {
+    let mut arr = DynArray::new();
+    for i in 0..100u32 {
+        arr.push(i);
+    }
+    assert_eq!(arr.len(), 100);
+    assert!(arr.capacity() >= 100);
+
+    arr.insert(0, 1000);
+    assert_eq!(arr[0], 1000);
+    assert_eq!(arr.remove(1), 0);
+    assert_eq!(arr.pop(), Some(99));
+    assert_eq!(arr.swap_remove(0), 1000);
+    assert_eq!(arr[0], 98);
+
+    arr.truncate(3);
+    assert_eq!(arr, [98, 1, 2]);
+    arr.extend_from_slice(&[7, 8]);
+    arr.retain(|v| v % 2 == 0);
+    assert_eq!(arr, [98, 2, 8]);
+
+    arr.clear();
+    assert!(arr.is_empty());
+    assert_eq!(arr.pop(), None);
+}
+
+#[test]
+fn drops_every_value_once() {
+    use core::cell::Cell;
+
+    struct Counted<'a>(&'a Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let mut arr = DynArray::with_capacity(2);
+    for _ in 0..10 {
+        arr.push(Counted(&drops));
+    }
+    arr.truncate(8);
+    assert_eq!(drops.get(), 2);
+    drop(arr.remove(0));
+    assert_eq!(drops.get(), 3);
+
+    let mut iter = arr.into_iter();
+    drop(iter.next());
+    assert_eq!(drops.get(), 4);
+    drop(iter);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn zero_sized_values() {
+    let mut arr = DynArray::new();
+    for _ in 0..1000 {
+        arr.push(());
+    }
+    assert_eq!(arr.len(), 1000);
+    assert_eq!(arr.into_iter().count(), 1000);
+}
+
+#[test]
+fn collect_and_clone() {
+    let arr: DynArray<u64> = (0..16).collect();
+    let copy = arr.clone();
+    assert_eq!(arr, copy);
+    assert_eq!(copy.iter().sum::<u64>(), 120);
+
+    let mut shrunk = DynArray::from([1u8, 2, 3]);
+    shrunk.reserve(100);
+    shrunk.shrink_to_fit();
+    assert_eq!(shrunk.capacity(), 3);
+}
+
+#[test]
+fn retain_survives_a_panicking_predicate() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let shared = Rc::new(());
+    let mut arr: DynArray<Rc<()>> = (0..6).map(|_| Rc::clone(&shared)).collect();
+    let mut calls = 0;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        arr.retain(|_| {
+            calls += 1;
+            assert!(calls != 4, "predicate failed");
+            calls % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+    // Visited: drop, keep, drop. The panicking element and the rest survive.
+    assert_eq!(arr.len(), 4);
+    assert_eq!(Rc::strong_count(&shared), 5);
+    drop(arr);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
@@ -0,0 +1,123 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+use core::alloc::GlobalAlloc;
+
+#[cfg(test)]
+fn region<const N: usize>() -> &'static mut [u8] {
+    // Each call leaks a fresh region, so tests never share memory.
+    Box::leak(Box::new([0u8; N]))
+}
+
+#[test]
+fn allocate_and_free() {
+    let mut heap = Heap::from_slice(region::<1024>());
+    let total = heap.stats().total;
+    assert!(total > 1000);
+
+    let a = heap.allocate(Layout::new::<u64>()).unwrap();
+    let b = heap.allocate(Layout::from_size_align(100, 64).unwrap()).unwrap();
+    assert!(b.as_ptr().addr().is_multiple_of(64));
+    assert!(heap.stats().used >= 8 + 100);
+
+    #[allow(unsafe_code)]
+    unsafe {
+        heap.deallocate(a, Layout::new::<u64>());
+        heap.deallocate(b, Layout::from_size_align(100, 64).unwrap());
+    }
+    let stats = heap.stats();
+    assert_eq!(stats.used, 0);
+    assert_eq!(stats.free, total);
+    assert_eq!(stats.free_blocks, 1);
+    assert_eq!(stats.fragmentation(), 0.0);
+}
+
+#[test]
+fn out_of_memory_and_extend() {
+    let mut heap = Heap::from_slice(region::<256>());
+    let layout = Layout::from_size_align(512, 8).unwrap();
+    assert_eq!(heap.allocate(layout), Err(AllocError::OutOfMemory(layout)));
+
+    heap.extend_from_slice(region::<1024>());
+    assert!(heap.allocate(layout).is_ok());
+    assert!(heap.stats().total >= 1024 + 240);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn fragmentation_and_best_fit() {
+    let mut heap = Heap::from_slice(region::<4096>());
+    let small = Layout::from_size_align(32, 16).unwrap();
+    let large = Layout::from_size_align(256, 16).unwrap();
+    let blocks: Vec<_> = (0..8).map(|i| heap.allocate(if i % 2 == 0 { large } else { small }).unwrap()).collect();
+
+    unsafe {
+        heap.deallocate(blocks[0], large);
+        heap.deallocate(blocks[3], small);
+    }
+    let stats = heap.stats();
+    assert_eq!(stats.free_blocks, 3);
+    assert!(stats.fragmentation() > 0.0);
+
+    heap.set_strategy(FitStrategy::BestFit);
+    let fit = heap.allocate(small).unwrap();
+    assert_eq!(fit, blocks[3]);
+
+    heap.set_strategy(FitStrategy::FirstFit);
+    let first = heap.allocate(small).unwrap();
+    assert_eq!(first, blocks[0]);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn freed_neighbours_merge() {
+    let mut heap = Heap::from_slice(region::<1024>());
+    let layout = Layout::from_size_align(64, 16).unwrap();
+    let blocks: Vec<_> = (0..4).map(|_| heap.allocate(layout).unwrap()).collect();
+    unsafe {
+        heap.deallocate(blocks[1], layout);
+        heap.deallocate(blocks[3], layout);
+        assert_eq!(heap.stats().free_blocks, 2);
+        heap.deallocate(blocks[2], layout);
+        assert_eq!(heap.stats().free_blocks, 1);
+        heap.deallocate(blocks[0], layout);
+    }
+    assert_eq!(heap.stats().free_blocks, 1);
+    assert_eq!(heap.stats().used, 0);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn static_locked_heap() {
+    static mut MEMORY: [u8; 2048] = [0; 2048];
+    static HEAP: LockedHeap = LockedHeap::empty();
+    let memory = &raw mut MEMORY;
+    HEAP.lock().extend_from_slice(unsafe { &mut *memory });
+
+    let layout = Layout::new::<[u32; 4]>();
+    let ptr = unsafe { HEAP.alloc(layout) };
+    assert!(!ptr.is_null());
+    assert!(HEAP.lock().stats().used >= 16);
+    unsafe { HEAP.dealloc(ptr, layout) };
+    assert_eq!(HEAP.lock().stats().used, 0);
+
+    let mut array = DynArray::new_in(&HEAP);
+    array.extend(0..100u32);
+    assert_eq!(array.iter().sum::<u32>(), 4950);
+}
+
+#[test]
+fn spin_locks() {
+    let lock = SpinLock::new(5);
+    {
+        let mut guard = lock.lock();
+        *guard += 1;
+        assert!(lock.is_locked());
+        assert!(lock.try_lock().is_none());
+    }
+    assert_eq!(*lock.try_lock().unwrap(), 6);
+    assert_eq!(lock.into_inner(), 6);
+}
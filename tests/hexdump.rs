@@ -0,0 +1,37 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+fn hexdump_rows() {
+    let dump = HexDump::new(b"Hello, world!\n");
+    assert_eq!(
+        dump.to_string(),
+        "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a       |Hello, world!.|"
+    );
+
+    let dump = HexDump::new(&[0u8; 12]).width(8).group(4).base(0x1000);
+    let expected = format!(
+        "{:016x}  00 00 00 00  00 00 00 00 |........|\n{:016x}  00 00 00 00              |....|",
+        0x1000, 0x1008
+    );
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(dump.to_string(), expected);
+    assert_eq!(HexDump::new(&[]).to_string(), "");
+}
+
+#[test]
+fn compact_forms() {
+    let bytes = [0xde, 0xad, 0xbe, 0xef];
+    assert_eq!(format!("{:x}", HexDump::new(&bytes)), "deadbeef");
+    assert_eq!(format!("{:#X}", HexDump::new(&bytes)), "0xDEADBEEF");
+    assert_eq!(format!("{:?}", HexDump::new(&bytes)), "[de ad be ef]");
+
+    let value = u32::from_be_bytes(bytes);
+    let object = ByteObject::from(&value);
+    assert_eq!(format!("{:x}", object), format!("{:x}", HexDump::new(&value.to_ne_bytes())));
+    assert_eq!(object.hexdump().to_string().len(), 2 * std::mem::size_of::<usize>() + 2 + 16 * 3 + 1 + 2 + 4);
+    assert!(format!("{:?}", object).contains("bytes: ["));
+    assert!(format!("{:#?}", object).contains("|....|"));
+}
@@ -0,0 +1,73 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+fn reader_reads_both_byte_orders() {
+    let buf = [0x12, 0x34, 0x56, 0x78, 0xaa, 0, 0, 0, 1, 2, 3];
+    let mut reader = ByteReader::new(&buf);
+
+    assert_eq!(reader.read_u16_le().unwrap(), 0x3412);
+    assert_eq!(reader.read_u16_be().unwrap(), 0x5678);
+    assert_eq!(reader.read_u8().unwrap(), 0xaa);
+    reader.align_to(4).unwrap();
+    assert_eq!(reader.position(), 8);
+    assert_eq!(reader.read_array::<2>().unwrap(), [1, 2]);
+    assert_eq!(reader.remaining(), 1);
+
+    assert_eq!(reader.read_u32_be().unwrap_err(), UnderrunError { needed: 4, remaining: 1 });
+    assert_eq!(reader.position(), 10);
+    reader.skip(1).unwrap();
+    assert!(reader.is_empty());
+    assert!(reader.skip(1).is_err());
+}
+
+#[test]
+fn writer_round_trips_through_reader() {
+    let mut buf = [0u8; 20];
+    let mut writer = ByteWriter::new(&mut buf);
+    writer.write_u64_be(0x0102_0304_0506_0708).unwrap();
+    writer.write_i16_le(-2).unwrap();
+    writer.align_to(4).unwrap();
+    writer.write_be(1.5f32).unwrap();
+    assert_eq!(writer.remaining(), 4);
+    assert!(writer.write_u64_le(0).is_err());
+    assert_eq!(writer.position(), 16);
+
+    assert_eq!(&buf[..10], &[1, 2, 3, 4, 5, 6, 7, 8, 0xfe, 0xff]);
+    let mut reader = ByteReader::new(&buf);
+    assert_eq!(reader.read_u64_be().unwrap(), 0x0102_0304_0506_0708);
+    assert_eq!(reader.read_le::<i16>().unwrap(), -2);
+    reader.skip(2).unwrap();
+    assert_eq!(reader.read_f32_be().unwrap(), 1.5);
+}
+
+#[test]
+fn endian_fields() {
+    #[derive(Debug, Pod)]
+    #[repr(C)]
+    struct Header {
+        magic: BigEndian<u32>,
+        length: LittleEndian<u16>,
+        flags: u16,
+    }
+
+    let header = Header { magic: BigEndian::new(0xcafe_babe), length: 3.into(), flags: 0 };
+    assert_eq!(&header.as_bytes()[..6], &[0xca, 0xfe, 0xba, 0xbe, 3, 0]);
+
+    let parsed: &Header = ref_from_bytes(header.as_bytes()).unwrap();
+    assert_eq!(parsed.magic.get(), 0xcafe_babe);
+    assert_eq!(parsed.length.get(), 3);
+    assert_eq!(format!("{:?}", parsed.magic), "BigEndian(3405691582)");
+}
+
+#[test]
+fn readers_over_objects_and_guards() {
+    let value = 0x0102u16.to_be();
+    assert_eq!(ByteObject::from(&value).reader().read_u16_be().unwrap(), 0x0102);
+
+    let mut guard = ByteGuard::<[u8]>::from_slice(&[0; 4]);
+    guard.writer().write_u32_le(7).unwrap();
+    assert_eq!(guard.reader().read_u32_le().unwrap(), 7);
+}
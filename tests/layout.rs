@@ -0,0 +1,91 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+#[derive(MemLayout)]
+#[repr(C)]
+struct Entry {
+    kind: u8,
+    address: u64,
+    flags: [u16; 2],
+}
+
+#[test]
+fn derive_describes_fields_and_padding() {
+    let names: Vec<_> = Entry::FIELDS.iter().map(|f| (f.name, f.ty, f.offset, f.size, f.align)).collect();
+    assert_eq!(names, [("kind", "u8", 0, 1, 1), ("address", "u64", 8, 8, 8), ("flags", "[u16; 2]", 16, 4, 2)]);
+    assert_eq!(
+        Entry::PADDING,
+        &[PaddingHole { offset: 1, size: 7 }, PaddingHole { offset: 20, size: 4 }]
+    );
+    assert!(Entry::has_padding());
+    assert_eq!(Entry::field_at(9).map(|f| f.name), Some("address"));
+    assert_eq!(Entry::field_at(3), None);
+}
+
+#[test]
+fn derive_supports_tuple_and_rust_layouts() {
+    #[derive(MemLayout)]
+    struct Pair(u32, u16, u16);
+
+    #[derive(MemLayout)]
+    #[repr(C)]
+    struct Packed {
+        a: u32,
+        b: u32,
+    }
+
+    assert_eq!(Pair::FIELDS[2].name, "2");
+    assert_eq!(Pair::FIELDS.iter().map(|f| f.size).sum::<usize>(), core::mem::size_of::<Pair>());
+    assert!(!Pair::has_padding());
+    assert!(Packed::PADDING.is_empty());
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn offset_and_container_of() {
+    #[repr(C)]
+    struct Inner {
+        a: u16,
+        b: u16,
+    }
+
+    #[repr(C)]
+    struct Outer {
+        head: u32,
+        inner: Inner,
+    }
+
+    assert_eq!(offset_of!(Outer, inner), 4);
+    assert_eq!(offset_of!(Outer, inner.b), 6);
+
+    let mut outer = Outer { head: 1, inner: Inner { a: 2, b: 3 } };
+    let b: *mut u16 = &mut outer.inner.b;
+    let parent = container_of!(b, Outer, inner.b);
+    unsafe { (*parent).head = 9 };
+    assert_eq!(outer.head, 9);
+    assert_eq!(outer.inner.a + outer.inner.b, 5);
+}
+
+#[test]
+fn field_aware_dumps_and_diffs() {
+    let old = Entry { kind: 1, address: 0x1000, flags: [0; 2] };
+    let new = Entry { kind: 1, address: 0x1000, flags: [0, 5] };
+
+    let dump = ByteObject::from(&old).field_dump().to_string();
+    let lines: Vec<_> = dump.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "0000  kind: u8        = 01");
+    assert!(lines[1].starts_with("0001  <padding>       ="));
+    assert!(lines[2].starts_with("0008  address: u64    ="));
+    assert!(lines[3].starts_with("0010  flags: [u16; 2] ="));
+    assert!(lines[4].starts_with("0014  <padding>"));
+
+    let old_bytes = ByteObject::from(&old);
+    let new_bytes = ByteObject::from(&new);
+    let diff = old_bytes.diff(&new_bytes);
+    let changed: Vec<_> = diff.changed_fields::<Entry>().map(|f| f.name).collect();
+    assert_eq!(changed, ["flags"]);
+}
@@ -1 +1,20 @@
-mod bytes;
\ No newline at end of file
+mod allocator;
+mod arena;
+mod buddy;
+mod bytes;
+mod cast;
+mod diff;
+mod dynarray;
+mod heap;
+mod hexdump;
+mod io;
+mod layout;
+mod list;
+mod mem;
+mod nulls;
+mod pointers;
+mod rel;
+mod reveal;
+mod slab;
+mod slotmap;
+mod tagged;
@@ -0,0 +1,146 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+use core::pin::{pin, Pin};
+
+#[cfg(test)]
+#[derive(Debug)]
+struct Node {
+    value: u32,
+    link: ListLink,
+}
+
+#[cfg(test)]
+impl Node {
+    const fn new(value: u32) -> Self {
+        Self { value, link: ListLink::new() }
+    }
+}
+
+#[cfg(test)]
+intrusive_adapter!(NodeAdapter = Node { link });
+
+#[cfg(test)]
+fn values(list: &IntrusiveList<Node, NodeAdapter>) -> Vec<u32> {
+    list.iter().map(|node| node.value).collect()
+}
+
+#[test]
+fn push_and_pop() {
+    let a = pin!(Node::new(1));
+    let b = pin!(Node::new(2));
+    let c = pin!(Node::new(3));
+    let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+    assert!(list.is_empty());
+    list.push_back(a.as_ref());
+    list.push_back(b.as_ref());
+    list.push_front(c.as_ref());
+    assert_eq!(list.len(), 3);
+    assert_eq!(values(&list), [3, 1, 2]);
+    assert_eq!(list.iter().rev().map(|node| node.value).collect::<Vec<_>>(), [2, 1, 3]);
+    assert_eq!(list.front().unwrap().value, 3);
+    assert_eq!(list.back().unwrap().value, 2);
+
+    assert_eq!(list.pop_front().unwrap().value, 3);
+    assert_eq!(list.pop_back().unwrap().value, 2);
+    assert!(!c.link.is_linked());
+    assert!(a.link.is_linked());
+    assert_eq!(values(&list), [1]);
+    assert_eq!(list.pop_back().unwrap().value, 1);
+    assert!(list.pop_front().is_none());
+    assert!(list.is_empty());
+}
+
+#[test]
+fn static_nodes() {
+    static FIRST: Node = Node::new(10);
+    static SECOND: Node = Node::new(20);
+    let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+    list.push_back(Pin::static_ref(&FIRST));
+    list.push_back(Pin::static_ref(&SECOND));
+    assert_eq!(values(&list), [10, 20]);
+}
+
+#[test]
+fn byte_guard_nodes() {
+    let nodes: Vec<_> = (0..4).map(|i| ByteGuard::pin(Node::new(i))).collect();
+    let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+    for node in &nodes {
+        list.push_front(node.as_ref());
+    }
+    assert_eq!(values(&list), [3, 2, 1, 0]);
+}
+
+#[test]
+fn cursor_remove_and_insert() {
+    let nodes: Vec<_> = (0..5).map(|i| ByteGuard::pin(Node::new(i))).collect();
+    let extra = pin!(Node::new(9));
+    let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+    for node in &nodes {
+        list.push_back(node.as_ref());
+    }
+
+    let mut cursor = list.cursor_front_mut();
+    while let Some(node) = cursor.current() {
+        if node.value % 2 == 1 {
+            assert_eq!(cursor.remove_current().unwrap().value, node.value);
+        } else {
+            cursor.move_next();
+        }
+    }
+    assert!(cursor.current().is_none());
+    cursor.insert_after(extra.as_ref());
+    cursor.move_prev();
+    assert_eq!(cursor.current().unwrap().value, 4);
+    cursor.insert_before(nodes[3].as_ref());
+    assert_eq!(values(&list), [9, 0, 2, 3, 4]);
+    assert!(!nodes[1].link.is_linked());
+
+    let mut cursor = list.cursor_back_mut();
+    cursor.move_next();
+    assert!(cursor.current().is_none());
+    cursor.move_next();
+    assert_eq!(cursor.current().unwrap().value, 9);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn unlink_in_the_middle() {
+    let nodes: Vec<_> = (0..3).map(|i| ByteGuard::pin(Node::new(i))).collect();
+    let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+    for node in &nodes {
+        list.push_back(node.as_ref());
+    }
+    unsafe { list.unlink(nodes[1].as_ref()) };
+    assert_eq!(values(&list), [0, 2]);
+    assert!(!nodes[1].link.is_linked());
+    list.push_back(nodes[1].as_ref());
+    assert_eq!(values(&list), [0, 2, 1]);
+}
+
+#[test]
+fn dropping_the_list_unlinks_nodes() {
+    let a = pin!(Node::new(1));
+    {
+        let mut list = IntrusiveList::<Node, NodeAdapter>::new();
+        list.push_back(a.as_ref());
+        assert!(a.link.is_linked());
+    }
+    assert!(!a.link.is_linked());
+    let mut other = IntrusiveList::<Node, NodeAdapter>::new();
+    other.push_back(a.as_ref());
+    assert_eq!(format!("{other:?}").matches("value: 1").count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "already linked")]
+fn pushing_a_linked_node_panics() {
+    let a = pin!(Node::new(1));
+    let mut first = IntrusiveList::<Node, NodeAdapter>::new();
+    let mut second = IntrusiveList::<Node, NodeAdapter>::new();
+    first.push_back(a.as_ref());
+    second.push_back(a.as_ref());
+}
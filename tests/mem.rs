@@ -0,0 +1,93 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+mod private {
+    #[repr(C)]
+    pub struct Secret {
+        key: u32,
+        id: u16,
+        tag: u8,
+    }
+
+    impl Secret {
+        pub fn new() -> Self {
+            Self { key: 7, id: 2, tag: 1 }
+        }
+    }
+
+    #[repr(C)]
+    pub struct Wrapper<T> {
+        value: T,
+        count: usize,
+    }
+
+    impl<T> Wrapper<T> {
+        pub fn new(value: T) -> Self {
+            Self { value, count: 1 }
+        }
+    }
+
+    #[repr(C)]
+    pub struct Pair(u64, u64);
+
+    impl Pair {
+        pub fn new() -> Self {
+            Self(3, 4)
+        }
+    }
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn reveal_by_value_and_reference() {
+    let secret = private::Secret::new();
+    let revealed = unsafe { mem!(&secret as { key: u32, id: u16, tag: u8 }) };
+    assert_eq!((revealed.key, revealed.id, revealed.tag), (7, 2, 1));
+
+    let mut secret = secret;
+    let revealed = unsafe { mem!(&mut secret as { pub key: u32, id: u16, tag: u8, }) };
+    revealed.key = 9;
+    let revealed = unsafe { mem!(turn secret into { key: u32, id: u16, tag: u8, } named Open) };
+    assert_eq!(revealed.key, 9);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn reveal_tuples_and_generics() {
+    let pair = private::Pair::new();
+    let revealed = unsafe { mem!(pair as (u64, u64)) };
+    assert_eq!(revealed.0 + revealed.1, 7);
+
+    fn value_of<T>(wrapper: private::Wrapper<T>) -> T {
+        let revealed = unsafe { mem!(wrapper as <T> { value: T, count: usize }) };
+        revealed.value
+    }
+    assert_eq!(value_of(private::Wrapper::new(5u8)), 5);
+    assert_eq!(value_of(private::Wrapper::new([1u64; 3])), [1; 3]);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn casts() {
+    let value = 1.0f32;
+    let bits = unsafe { mem!(value as u32) };
+    assert_eq!(bits, 1.0f32.to_bits());
+
+    let mut value = [0u8; 4];
+    *unsafe { mem!(&mut value as [u8; 4]) } = [1; 4];
+    assert_eq!(unsafe { mem!(&value as [u8; 4]) }, &[1; 4]);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn by_value_casts_ignore_alignment() {
+    let n = 0x0403_0201u32;
+    let bytes = unsafe { mem!(n as [u8; 4]) };
+    assert_eq!(bytes, 0x0403_0201u32.to_ne_bytes());
+    assert_eq!(unsafe { mem!(bytes as u32) }, n);
+    let halves = [1u16; 4];
+    assert_eq!(unsafe { transmute_checked::<[u16; 4], u64>(halves) }, 0x0001_0001_0001_0001);
+}
@@ -0,0 +1,45 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+#[allow(unsafe_code)]
+fn nullable_pointers() {
+    let mut value = 5u32;
+    let ptr = Nullable::from_mut(&mut value);
+    assert!(!ptr.is_null());
+    *unsafe { ptr.as_mut() }.unwrap() += 1;
+    assert_eq!(unsafe { ptr.as_ref() }, Some(&6));
+
+    let null = Nullable::<u32>::null();
+    assert!(null.is_null());
+    assert_eq!(unsafe { null.as_ref() }, None);
+    assert_eq!(null, Nullable::default());
+    assert_eq!(Option::<core::ptr::NonNull<u32>>::from(null), None);
+    assert_eq!(Nullable::from(Null::<u32>::new()), null);
+
+    let bytes = ptr.cast::<u8>();
+    assert_eq!(bytes.as_ptr() as usize, ptr.as_ptr() as usize);
+    assert!(null.map(|p| p.cast::<u8>()).is_null());
+    assert_eq!(Nullable::from(ptr.to_non_null()), ptr);
+    assert_eq!(format!("{:?}", null), "null");
+}
+
+#[test]
+fn maybe_undefined_states() {
+    let value = MaybeUndefined::Value(2);
+    assert_eq!(value.map(|v| v * 2), MaybeUndefined::Value(4));
+    assert_eq!(MaybeUndefined::<u8>::Null.map(|v| v * 2), MaybeUndefined::Null);
+    assert_eq!(MaybeUndefined::<u8>::default(), MaybeUndefined::from(Undefined));
+
+    assert_eq!(MaybeUndefined::from(None::<u8>), MaybeUndefined::Null);
+    assert_eq!(MaybeUndefined::<u8>::Undefined.or(value), value);
+    assert_eq!(MaybeUndefined::Null.or(value), MaybeUndefined::Null);
+    assert_eq!(MaybeUndefined::<u8>::Null.defined(), Some(None));
+    assert_eq!(MaybeUndefined::<u8>::Undefined.defined(), None);
+    assert_eq!(MaybeUndefined::<u8>::Undefined.unwrap_or(7), 7);
+
+    let null: MaybeUndefined<_> = Nullable::<u8>::null().into();
+    assert!(null.is_null());
+}
@@ -0,0 +1,46 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+#[allow(unused)]
+use memutils::prelude::*;
+
+#[test]
+fn alignment() {
+    let ptr = core::ptr::without_provenance::<u8>(0x1003);
+    assert_eq!(ptr.align_up(8).addr(), 0x1008);
+    assert_eq!(ptr.align_down(8).addr(), 0x1000);
+    assert!(!ptr.is_aligned_by(2));
+    assert!(ptr.align_up(16).is_aligned_by(16));
+    assert_eq!(ptr.align_up(1), ptr);
+}
+
+#[test]
+fn checked_arithmetic() {
+    let ptr = core::ptr::without_provenance_mut::<u32>(usize::MAX - 7);
+    assert_eq!(ptr.checked_add(1).map(|p| p.addr()), Some(usize::MAX - 3));
+    assert_eq!(ptr.checked_add(2), None);
+    assert_eq!(ptr.checked_byte_add(8), None);
+    assert_eq!(core::ptr::without_provenance::<u64>(8).checked_sub(2), None);
+    assert_eq!(core::ptr::without_provenance::<u64>(16).checked_sub(2).map(|p| p.addr()), Some(0));
+
+    let values = [0u16; 4];
+    let start = values.as_ptr();
+    let end = start.wrapping_add(4);
+    assert_eq!(start.distance_to(end), 8);
+    assert_eq!(end.distance_to(start), -8);
+    assert_eq!(start.cast_bytes().addr(), start.addr());
+}
+
+#[test]
+fn stride_walks_a_buffer() {
+    let values = [1u32, 2, 3, 4, 5];
+    let start = values.as_ptr();
+    let end = start.wrapping_add(values.len());
+
+    let every_other = start.stride_to(end, 8);
+    assert_eq!(every_other.len(), 3);
+    let offsets: Vec<isize> = every_other.map(|p| start.distance_to(p)).collect();
+    assert_eq!(offsets, [0, 8, 16]);
+    assert_eq!(end.stride_to(start, 4).count(), 0);
+}
@@ -0,0 +1,119 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+#[repr(C)]
+#[derive(FromBytes, AsBytes)]
+struct Snapshot {
+    first: RelPtr<u32>,
+    items: RelSlice<u32>,
+    values: [u32; 4],
+}
+
+#[cfg(test)]
+impl Snapshot {
+    fn new() -> Self {
+        let mut snapshot = Snapshot { first: RelPtr::null(), items: RelSlice::empty(), values: [1, 2, 3, 4] };
+        let first: *const u32 = &snapshot.values[0];
+        let items: *const [u32] = &snapshot.values[1..3];
+        snapshot.first.set(first);
+        snapshot.items.set(items);
+        snapshot
+    }
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn relative_pointers() {
+    let mut pair = (RelPtr::<u64, i8>::null(), 5u64);
+    assert!(pair.0.is_null());
+    assert_eq!(unsafe { pair.0.get() }, None);
+
+    let target: *const u64 = &pair.1;
+    pair.0.set(target);
+    assert_eq!(pair.0.offset(), 8);
+    assert_eq!(pair.0.as_ptr(), target);
+    *unsafe { pair.0.get_mut() }.unwrap() += 1;
+    assert_eq!(pair.1, 6);
+
+    pair.0.set(core::ptr::null());
+    assert!(pair.0.is_null());
+}
+
+#[test]
+fn relative_pointer_errors() {
+    let mut ptr = RelPtr::<u8, i8>::null();
+    let own: *const u8 = (&ptr as *const RelPtr<u8, i8>).cast();
+    assert_eq!(ptr.try_set(own), Err(RelPtrError::SelfReference));
+
+    let far = [0u8; 300];
+    assert!(matches!(ptr.try_set(&far[299]), Err(RelPtrError::Overflow { .. })));
+    assert!(ptr.is_null());
+
+    let mut slice = RelSlice::<u8, i8>::empty();
+    assert_eq!(slice.try_set(&far[..200]), Err(RelPtrError::LengthOverflow { len: 200 }));
+    assert!(slice.is_empty());
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn snapshots_are_relocatable() {
+    let snapshot = Snapshot::new();
+    let bytes = ByteObject::from(&snapshot);
+    let copy: Snapshot = read_from_bytes(bytes.bytes()).unwrap();
+
+    assert_ne!(copy.first.as_ptr(), snapshot.first.as_ptr());
+    assert_eq!(unsafe { copy.first.get() }, Some(&1));
+    assert_eq!(unsafe { copy.items.get() }, &[2, 3]);
+    assert_eq!(copy.items.len(), 2);
+
+    let zeroed = Snapshot::zeroed();
+    assert!(zeroed.first.is_null());
+    assert_eq!(unsafe { zeroed.items.get() }, &[] as &[u32]);
+}
+
+#[test]
+#[allow(unsafe_code)]
+fn relative_dynarray() {
+    let mut block = (RelDynArray::<u16, i16>::new(), [core::mem::MaybeUninit::<u16>::uninit(); 3]);
+    assert_eq!(block.0.capacity(), 0);
+    unsafe { block.0.set_storage(&mut block.1) };
+    assert_eq!(block.0.capacity(), 3);
+
+    unsafe {
+        block.0.push(1).unwrap();
+        block.0.push(2).unwrap();
+        block.0.push(3).unwrap();
+        assert_eq!(block.0.push(4), Err(4));
+        assert!(block.0.is_full());
+        block.0.as_mut_slice()[0] = 10;
+        assert_eq!(block.0.as_slice(), &[10, 2, 3]);
+        assert_eq!(block.0.pop(), Some(3));
+        assert_eq!(block.0.to_dynarray(), [10, 2]);
+    }
+
+    let mut moved = block;
+    assert_eq!(unsafe { moved.0.as_slice() }, &[10, 2]);
+    unsafe { moved.0.clear() };
+    assert!(moved.0.is_empty());
+    assert_eq!(unsafe { moved.0.pop() }, None);
+}
+
+#[test]
+fn negative_lengths_read_as_empty() {
+    let mut bytes = [0u8; 6];
+    bytes[2..4].copy_from_slice(&5i16.to_ne_bytes());
+    bytes[4..6].copy_from_slice(&(-3i16).to_ne_bytes());
+    let array: RelDynArray<u16, i16> = read_from_bytes(&bytes).unwrap();
+    assert_eq!(array.capacity(), 5);
+    assert_eq!(array.len(), 0);
+    assert!(array.is_empty());
+    assert!(!array.is_full());
+
+    bytes[2..4].copy_from_slice(&(-1i16).to_ne_bytes());
+    let array: RelDynArray<u16, i16> = read_from_bytes(&bytes).unwrap();
+    assert_eq!(array.capacity(), 0);
+    assert!(array.is_full());
+}
@@ -0,0 +1,104 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+mod library {
+    use memutils::*;
+
+    #[derive(Reveal)]
+    #[repr(C)]
+    pub struct Counter {
+        /// The current count.
+        count: u32,
+        step: u16,
+    }
+
+    impl Counter {
+        pub fn new() -> Self {
+            Self { count: 0, step: 2 }
+        }
+
+        pub fn tick(&mut self) {
+            self.count += self.step as u32;
+        }
+    }
+
+    #[derive(Reveal)]
+    #[repr(transparent)]
+    pub struct Id(u64);
+
+    impl Id {
+        pub fn new(id: u64) -> Self {
+            Self(id)
+        }
+    }
+
+    pub struct Opaque(pub u32);
+
+    #[derive(Reveal)]
+    #[repr(C)]
+    pub struct Handle {
+        raw: Opaque,
+        flags: u8,
+    }
+
+    impl Handle {
+        pub fn new(raw: u32) -> Self {
+            Self { raw: Opaque(raw), flags: 1 }
+        }
+    }
+
+    #[derive(Reveal)]
+    #[repr(C)]
+    pub struct Slot<T: Copy> {
+        value: T,
+        used: bool,
+    }
+
+    impl<T: Copy> Slot<T> {
+        pub fn new(value: T) -> Self {
+            Self { value, used: true }
+        }
+    }
+}
+
+#[test]
+fn reveal_private_fields() {
+    let mut counter = library::Counter::new();
+    counter.tick();
+    assert_eq!(counter.reveal().count, 2);
+
+    counter.reveal_mut().step = 5;
+    counter.tick();
+    assert_eq!(counter.reveal().count, 7);
+
+    let library::CounterRevealed { count, step } = counter.into_revealed();
+    assert_eq!((count, step), (7, 5));
+    let counter = library::Counter::from_revealed(library::CounterRevealed { count: 1, step: 1 });
+    assert_eq!(counter.reveal().count, 1);
+}
+
+#[test]
+fn reveal_tuple_and_generic_structs() {
+    assert_eq!(library::Id::new(42).reveal().0, 42);
+
+    let slot = library::Slot::new(3.5f64);
+    assert_eq!(slot.reveal().value, 3.5);
+    assert!(slot.reveal().used);
+}
+
+#[test]
+fn revealed_twin_is_debug() {
+    let counter = library::Counter::new();
+    assert_eq!(format!("{:?}", counter.reveal()), "CounterRevealed { count: 0, step: 2 }");
+    assert_eq!(format!("{:?}", library::Slot::new(1u8).reveal()), "SlotRevealed { value: 1, used: true }");
+}
+
+#[test]
+fn reveal_fields_without_debug() {
+    let handle = library::Handle::new(9);
+    assert_eq!(handle.reveal().raw.0, 9);
+    assert_eq!(handle.reveal().flags, 1);
+}
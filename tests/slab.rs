@@ -0,0 +1,153 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn boxes_return_their_slots() {
+    let slab = Slab::<u64>::new().with_page_size(256);
+    let mut boxes: Vec<_> = (0..20).map(|i| slab.alloc(i)).collect();
+    *boxes[3] += 100;
+    assert_eq!(*boxes[3], 103);
+    assert!(SlabBox::as_ptr(&boxes[0]).is_aligned());
+
+    let stats = slab.stats();
+    assert_eq!(stats.used, 20);
+    assert_eq!(stats.slot_size, 16);
+    assert!(stats.pages >= 2);
+    assert_eq!(stats.slots, stats.pages * ((256 - 40) / 16));
+
+    let reused = SlabBox::as_ptr(&boxes[5]);
+    boxes.remove(5);
+    assert_eq!(slab.stats().used, 19);
+    let again = slab.alloc(7);
+    assert_eq!(SlabBox::as_ptr(&again), reused);
+}
+
+#[test]
+fn values_are_dropped() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Counted;
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let slab = Slab::new();
+    let a = slab.alloc(Counted);
+    let b = slab.alloc(Counted);
+    drop((a, b));
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+    assert_eq!(slab.stats().used, 0);
+}
+
+#[test]
+fn shrink_and_occupancy() {
+    let slab = Slab::<[u8; 48]>::new().with_page_size(128);
+    let mut boxes: Vec<_> = (0..6).map(|_| slab.alloc([0; 48])).collect();
+    let stats = slab.stats();
+    assert_eq!(stats.occupancy(), stats.used as f64 / stats.slots as f64);
+    assert!(stats.occupancy() > 0.5);
+
+    assert!(stats.pages > 1);
+    boxes.truncate(1);
+    slab.shrink();
+    assert_eq!(slab.stats().pages, 1);
+    drop(boxes);
+    slab.shrink();
+    assert_eq!(slab.stats(), SlabStats { slot_size: 56, ..SlabStats::default() });
+}
+
+#[test]
+fn constructed_values_are_cached() {
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    struct Buffer(Vec<u8>);
+    impl Drop for Buffer {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    fn build() -> Buffer {
+        BUILT.fetch_add(1, Ordering::Relaxed);
+        Buffer(Vec::with_capacity(64))
+    }
+
+    let slab = Slab::new().with_page_size(512).with_constructor(build);
+    assert_eq!(BUILT.load(Ordering::Relaxed), 0);
+
+    let mut first = slab.alloc_cached();
+    let built = BUILT.load(Ordering::Relaxed);
+    assert!(built > 1);
+    first.0.push(1);
+    let ptr = SlabBox::as_ptr(&first);
+    drop(first);
+
+    let again = slab.alloc_cached();
+    assert_eq!(SlabBox::as_ptr(&again), ptr);
+    assert_eq!(again.0, [1]);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+    assert_eq!(BUILT.load(Ordering::Relaxed), built);
+
+    drop(again);
+    drop(slab);
+    assert_eq!(DROPPED.load(Ordering::Relaxed), built);
+}
+
+#[test]
+fn pages_come_from_the_backing_allocator() {
+    let arena = Arena::new().with_chunk_size(8192);
+    let slab = Slab::new_in(&arena).with_page_size(1024);
+    let values: Vec<_> = (0..100u32).map(|i| slab.alloc(i)).collect();
+    assert_eq!(values.iter().map(|value| **value).sum::<u32>(), 4950);
+}
+
+#[test]
+#[should_panic(expected = "cannot hold")]
+fn tiny_pages_panic() {
+    let _ = Slab::<[u8; 64]>::new().with_page_size(64);
+}
+
+#[test]
+fn reset_runs_when_slots_are_returned() {
+    let slab = Slab::new().with_constructor(Vec::<u8>::new).with_reset(Vec::clear);
+    let mut first = slab.alloc_cached();
+    first.extend_from_slice(b"dirty");
+    let ptr = SlabBox::as_ptr(&first);
+    drop(first);
+
+    let again = slab.alloc_cached();
+    assert_eq!(SlabBox::as_ptr(&again), ptr);
+    assert!(again.is_empty());
+    assert!(again.capacity() >= 5);
+}
+
+#[test]
+fn panicking_constructor_frees_the_page() {
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    struct Fragile;
+    impl Drop for Fragile {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    fn build() -> Fragile {
+        assert!(BUILT.fetch_add(1, Ordering::Relaxed) != 3, "constructor failed");
+        Fragile
+    }
+
+    let slab = Slab::new().with_constructor(build);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(slab.alloc_cached())));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 3);
+    assert_eq!(slab.stats().pages, 0);
+
+    let value = slab.alloc_cached();
+    assert_eq!(slab.stats().used, 1);
+    drop(value);
+}
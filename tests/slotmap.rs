@@ -0,0 +1,127 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[test]
+fn handles_expire_on_remove() {
+    let mut map = SlotMap::new();
+    let a = map.insert("a");
+    let b = map.insert("b");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[a], "a");
+    assert_eq!(map.get(b), Some(&"b"));
+
+    assert_eq!(map.remove(a), Some("a"));
+    assert_eq!(map.remove(a), None);
+    assert!(!map.contains(a));
+
+    let c = map.insert("c");
+    assert_eq!(c.index(), a.index());
+    assert_eq!(c.generation(), a.generation() + 1);
+    assert_eq!(map.get(a), None);
+    assert_eq!(map[c], "c");
+    *map.get_mut(c).unwrap() = "see";
+    assert_eq!(map[c], "see");
+}
+
+#[test]
+fn insert_with_sees_its_handle() {
+    let mut map = SlotMap::with_capacity(4);
+    let handle = map.insert_with(|handle| handle.index() * 10);
+    assert_eq!(map[handle], 0);
+    let next = map.insert_with(|handle| handle.index() * 10);
+    assert_eq!(map[next], 10);
+    assert!(map.capacity() >= 4);
+}
+
+#[test]
+fn panicking_insert_with_keeps_the_free_slot() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut map = SlotMap::new();
+    let a = map.insert(1);
+    map.remove(a);
+    let result = catch_unwind(AssertUnwindSafe(|| map.insert_with(|_| panic!("no value"))));
+    assert!(result.is_err());
+    assert!(map.is_empty());
+
+    let b = map.insert(2);
+    assert_eq!(b.index(), a.index());
+}
+
+#[test]
+fn iteration_and_retain() {
+    let mut map = SlotMap::new();
+    let handles: Vec<_> = (0..6).map(|i| map.insert(i)).collect();
+    map.remove(handles[2]);
+
+    let iter = map.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.map(|(_, value)| *value).collect::<Vec<_>>(), [0, 1, 3, 4, 5]);
+    assert_eq!(map.handles().count(), 5);
+
+    for value in map.values_mut() {
+        *value *= 2;
+    }
+    for (handle, value) in &mut map {
+        *value += handle.index() as i32;
+    }
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), [0, 3, 9, 12, 15]);
+
+    map.retain(|_, value| *value % 2 == 1);
+    assert_eq!(map.len(), 3);
+    assert!(!map.contains(handles[0]));
+    assert_eq!(format!("{map:?}").matches("Handle").count(), 3);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert!(handles.iter().all(|&handle| map.get(handle).is_none()));
+}
+
+#[test]
+#[should_panic(expected = "invalid slot map handle")]
+fn stale_index_panics() {
+    let mut map = SlotMap::new();
+    let handle = map.insert(1);
+    map.remove(handle);
+    map.insert(2);
+    let _ = map[handle];
+}
+
+#[test]
+fn secondary_maps() {
+    let mut map = SlotMap::new();
+    let mut names = SecondaryMap::new();
+    let a = map.insert(1);
+    let b = map.insert(2);
+    assert_eq!(names.insert(b, "b"), None);
+    assert_eq!(names.insert(b, "bee"), Some("b"));
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[b], "bee");
+    assert!(!names.contains(a));
+
+    map.remove(b);
+    let c = map.insert(3);
+    assert_eq!(c.index(), b.index());
+    assert_eq!(names.get(c), None);
+    assert_eq!(names.insert(c, "c"), None);
+    assert_eq!(names.get(b), None);
+    assert_eq!(names.insert(b, "stale"), None);
+    assert_eq!(names[c], "c");
+
+    names.insert(a, "a");
+    assert_eq!(names.iter().map(|(_, name)| *name).collect::<Vec<_>>(), ["a", "c"]);
+    assert_eq!(names.remove(a), Some("a"));
+    assert_eq!(names.len(), 1);
+    names.clear();
+    assert!(names.is_empty());
+}
+
+#[test]
+fn slot_maps_in_arenas() {
+    let arena = Arena::new();
+    let mut map = SlotMap::new_in(&arena);
+    let handle = map.insert([1u8; 16]);
+    assert_eq!(map[handle][15], 1);
+}
@@ -0,0 +1,76 @@
+#![deny(unsafe_code)]
+
+#[allow(unused)]
+use memutils::*;
+
+#[cfg(test)]
+use core::sync::atomic::Ordering;
+
+#[test]
+fn tags_live_in_the_low_bits() {
+    let mut value = 7u64;
+    let raw: *mut u64 = &mut value;
+    let mut ptr = TaggedPtr::<u64, 3>::new(raw, 0b101);
+    assert_eq!(TaggedPtr::<u64, 3>::MASK, 0b111);
+    assert_eq!(ptr.ptr(), raw);
+    assert_eq!(ptr.tag(), 0b101);
+    assert_eq!(ptr.into_raw().addr(), raw.addr() | 0b101);
+    assert!(ptr.tag_bit(0) && !ptr.tag_bit(1));
+
+    ptr.set_tag_bit(1, true);
+    ptr.set_tag_bit(0, false);
+    assert_eq!(ptr.tag(), 0b110);
+    assert_eq!(ptr.with_tag(1).tag(), 1);
+    assert_eq!(TaggedPtr::from_raw(ptr.into_raw()), ptr);
+    assert!(TaggedPtr::<u64, 3>::null().with_tag(3).is_null());
+    assert_eq!(TaggedPtr::<u64, 3>::default(), TaggedPtr::null());
+}
+
+#[test]
+#[should_panic(expected = "does not fit")]
+fn oversized_tags_panic() {
+    let _ = TaggedPtr::<u32, 2>::null().with_tag(4);
+}
+
+#[test]
+#[should_panic(expected = "not aligned enough")]
+fn misaligned_pointers_panic() {
+    let bytes = [0u32; 2];
+    let raw = bytes.as_ptr().cast_mut().wrapping_byte_add(2);
+    let _ = TaggedPtr::<u32, 2>::new(raw, 0);
+}
+
+#[test]
+fn atomic_tagged_pointers() {
+    let mut value = 1u32;
+    let raw: *mut u32 = &mut value;
+    let atomic = AtomicTaggedPtr::new(TaggedPtr::<u32, 2>::new(raw, 1));
+    assert_eq!(atomic.load(Ordering::Acquire).tag(), 1);
+
+    let old = atomic.fetch_set_tag(2, Ordering::AcqRel);
+    assert_eq!(old.tag(), 1);
+    assert_eq!(atomic.load(Ordering::Acquire).tag(), 2);
+    atomic.fetch_set_tag_bit(0, true, Ordering::AcqRel);
+    assert_eq!(atomic.load(Ordering::Acquire).tag(), 3);
+    atomic.fetch_set_tag_bit(1, false, Ordering::AcqRel);
+    assert_eq!(atomic.load(Ordering::Acquire).ptr(), raw);
+
+    let current = atomic.load(Ordering::Acquire);
+    let stale = current.with_tag(0);
+    assert_eq!(atomic.compare_exchange(stale, TaggedPtr::null(), Ordering::AcqRel, Ordering::Acquire), Err(current));
+    assert_eq!(atomic.compare_exchange(current, TaggedPtr::null(), Ordering::AcqRel, Ordering::Acquire), Ok(current));
+    assert!(atomic.swap(current, Ordering::AcqRel).is_null());
+    assert_eq!(atomic.into_inner(), current);
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn high_bit_tags() {
+    let mut value = 3u8;
+    let raw: *mut u8 = &mut value;
+    let ptr = HighTaggedPtr::new(raw, 0xbeef);
+    assert_eq!(ptr.tag(), 0xbeef);
+    assert_eq!(ptr.ptr(), raw);
+    assert_eq!(ptr.with_tag(0).ptr(), raw);
+    assert_eq!(HighTaggedPtr::<u8>::default().tag(), 0);
+}
@@ -492,7 +492,7 @@ where
     /// subslice.index_mut(0).write(6);
     /// assert_eq!(subslice.index(0).read(), 6);
     /// ```
-    pub fn index_mut<'a, I>(&'a mut self, index: I) -> Volatile<&mut I::Output, A>
+    pub fn index_mut<'a, I>(&'a mut self, index: I) -> Volatile<&'a mut I::Output, A>
     where
         I: SliceIndex<[T]>,
         R: DerefMut,
@@ -878,7 +878,7 @@ mod tests {
         assert!(field_2.read());
         field_2.write(false);
         assert_eq!(volatile.map(|s| &s.field_1).read(), 61);
-        assert_eq!(volatile.map(|s| &s.field_2).read(), false);
+        assert!(!volatile.map(|s| &s.field_2).read());
     }
 
     #[cfg(feature = "unstable")]
@@ -565,6 +565,38 @@ pub const fn align_up(addr: u64, align: u64) -> u64 {
     }
 }
 
+/// Heaps that can be created over a mapped virtual memory range.
+#[cfg(feature = "memutils")]
+pub trait HeapFromVirtAddr: Sized {
+    /// Creates a heap that hands out the `size` bytes starting at `start`.
+    ///
+    /// ## Safety
+    ///
+    /// The range must be mapped writable, unused by anything else, and stay
+    /// that way for as long as the heap lives.
+    unsafe fn from_virt_addr(start: VirtAddr, size: usize) -> Self;
+}
+
+#[cfg(all(feature = "memutils", target_pointer_width = "64"))]
+impl HeapFromVirtAddr for memutilscore::Heap {
+    #[inline]
+    unsafe fn from_virt_addr(start: VirtAddr, size: usize) -> Self {
+        let mut heap = Self::empty();
+        unsafe { heap.extend(start.as_mut_ptr(), size) };
+        heap
+    }
+}
+
+#[cfg(all(feature = "memutils", target_pointer_width = "64"))]
+impl HeapFromVirtAddr for memutilscore::LockedHeap {
+    #[inline]
+    unsafe fn from_virt_addr(start: VirtAddr, size: usize) -> Self {
+        let heap = Self::empty();
+        unsafe { heap.lock().extend(start.as_mut_ptr(), size) };
+        heap
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -593,4 +625,23 @@ mod tests {
         assert_eq!(align_up(0, 2), 0);
         assert_eq!(align_up(0, 0x8000_0000_0000_0000), 0);
     }
+
+    #[test]
+    #[cfg(feature = "memutils")]
+    fn heap_from_virt_addr() {
+        use core::alloc::Layout;
+        use memutilscore::{Heap, LockedHeap};
+
+        let memory = vec![0u8; 4096].leak();
+        let start = VirtAddr::new(memory.as_mut_ptr() as u64);
+        let mut heap = unsafe { Heap::from_virt_addr(start, memory.len()) };
+        let block = heap.allocate(Layout::new::<[u64; 4]>()).unwrap();
+        assert!(block.as_ptr() as u64 >= start.as_u64());
+        assert!(heap.stats().used >= 32);
+
+        let memory = vec![0u8; 4096].leak();
+        let locked = unsafe { LockedHeap::from_virt_addr(VirtAddr::new(memory.as_mut_ptr() as u64), 4096) };
+        let stats = locked.lock().stats();
+        assert_eq!((stats.total, stats.free), (4096, 4096));
+    }
 }
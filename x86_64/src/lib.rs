@@ -14,6 +14,8 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
 pub use crate::addr::{align_down, align_up, PhysAddr, VirtAddr};
+#[cfg(feature = "memutils")]
+pub use crate::addr::HeapFromVirtAddr;
 
 /// Makes a function const only when `feature = "const_fn"` is enabled.
 ///
@@ -20,3 +20,70 @@ pub trait FrameDeallocator<S: PageSize> {
     /// The caller must ensure that the passed frame is unused.
     unsafe fn deallocate_frame(&mut self, frame: PhysFrame<S>);
 }
+
+/// Hands out frames as buddy blocks of the frame's order.
+///
+/// Every block of an order lies at the same offset from a multiple of its size,
+/// so the managed region must start aligned to the frame size (or the allocator's
+/// minimum order must be at least the frame's order). Otherwise no frame of that
+/// size can be handed out and `allocate_frame` always returns `None`.
+#[cfg(feature = "memutils")]
+unsafe impl<S: PageSize> FrameAllocator<S> for memutilscore::BuddyAllocator<'_> {
+    fn allocate_frame(&mut self) -> Option<PhysFrame<S>> {
+        let order = S::SIZE.trailing_zeros();
+        let addr = self.allocate_block(order)?;
+        match PhysFrame::from_start_address(crate::PhysAddr::new(addr as u64)) {
+            Ok(frame) => Some(frame),
+            Err(_) => {
+                // The region is not aligned to the frame size, so neither this
+                // block nor any other of its order is usable as a frame.
+                unsafe { self.deallocate_block(addr, order) };
+                None
+            }
+        }
+    }
+}
+
+#[cfg(feature = "memutils")]
+impl<S: PageSize> FrameDeallocator<S> for memutilscore::BuddyAllocator<'_> {
+    unsafe fn deallocate_frame(&mut self, frame: PhysFrame<S>) {
+        unsafe { self.deallocate_block(frame.start_address().as_u64() as usize, S::SIZE.trailing_zeros()) }
+    }
+}
+
+#[cfg(all(test, feature = "memutils"))]
+mod tests {
+    use super::*;
+    use crate::structures::paging::{Size2MiB, Size4KiB};
+    use memutilscore::BuddyAllocator;
+
+    const SIZE: usize = 0x40_0000;
+
+    #[test]
+    fn buddy_allocator_frames() {
+        let mut bitmap = [0; BuddyAllocator::bitmap_words(SIZE, 12, 22)];
+        let mut buddy = unsafe { BuddyAllocator::from_addr(0x40_0000, SIZE, 12, 22, &mut bitmap) };
+
+        let small: PhysFrame<Size4KiB> = buddy.allocate_frame().unwrap();
+        let huge: PhysFrame<Size2MiB> = buddy.allocate_frame().unwrap();
+        assert_eq!(small.start_address().as_u64(), 0x40_0000);
+        assert_eq!(huge.start_address().as_u64(), 0x60_0000);
+        assert!(FrameAllocator::<Size2MiB>::allocate_frame(&mut buddy).is_none());
+
+        unsafe {
+            buddy.deallocate_frame(small);
+            buddy.deallocate_frame(huge);
+        }
+        assert_eq!(buddy.free_bytes(), SIZE);
+    }
+
+    #[test]
+    fn buddy_allocator_misaligned_region() {
+        let mut bitmap = [0; BuddyAllocator::bitmap_words(SIZE, 12, 22)];
+        let mut buddy = unsafe { BuddyAllocator::from_addr(0x1000, SIZE, 12, 22, &mut bitmap) };
+
+        assert!(FrameAllocator::<Size2MiB>::allocate_frame(&mut buddy).is_none());
+        assert_eq!(buddy.free_bytes(), SIZE);
+        assert!(FrameAllocator::<Size4KiB>::allocate_frame(&mut buddy).is_some());
+    }
+}